@@ -1,4 +1,7 @@
+use std::cmp::Ordering;
+
 use crate::typings::{AirdropGameConstants, GameConstants, PlayerGameConstants};
+use strum::IntoEnumIterator;
 use strum_macros::{EnumCount, EnumIter};
 
 pub enum TeamSize {
@@ -146,6 +149,8 @@ pub const GAME_CONSTANTS: GameConstants = GameConstants {
     },
 };
 
+/// Render layers, from bottom to top; the derived ordering follows the declaration order
+#[derive(Debug, Hash, Eq, PartialEq, Ord, PartialOrd, Copy, Clone, EnumCount, EnumIter)]
 pub enum ZIndexes {
     Ground,
     UnderWaterDeathMarkers,
@@ -186,3 +191,26 @@ pub enum ZIndexes {
     Emotes,
     Gas
 }
+
+impl ZIndexes {
+    pub fn as_u8(self) -> u8 {
+        self as u8
+    }
+
+    pub fn from_u8(value: u8) -> Option<ZIndexes> {
+        ZIndexes::try_from_index(value as usize)
+    }
+
+    pub fn as_index(self) -> usize {
+        self as usize
+    }
+
+    pub fn try_from_index(index: usize) -> Option<ZIndexes> {
+        ZIndexes::iter().nth(index)
+    }
+
+    /// Compares two layers by the order they're rendered in, bottom first
+    pub fn render_order(&self, other: &ZIndexes) -> Ordering {
+        self.cmp(other)
+    }
+}
@@ -0,0 +1,7 @@
+pub mod throwable;
+pub mod parachute;
+pub mod particle_emitter;
+pub mod decal;
+pub mod death_marker;
+pub mod building;
+pub mod obstacle;
@@ -0,0 +1,35 @@
+use crate::utils::hitbox::{hitbox_contains, hitboxes_collide, CircleHitbox, Hitbox};
+use crate::utils::vectors::Vec2D;
+
+/// How far from a building's floor the ceiling already starts hiding, so that it's
+/// out of the way by the time the player walks through the entrance
+pub const CEILING_HIDE_MARGIN: f64 = 2.0;
+
+#[derive(Debug, Clone)]
+pub struct Building {
+    /// The building's walls and other solid parts
+    pub outline: Hitbox,
+    /// The area players can walk around in, under the ceiling
+    pub floor: Hitbox,
+}
+
+impl Building {
+    pub fn new(outline: Hitbox, floor: Hitbox) -> Building {
+        Building { outline, floor }
+    }
+
+    /// Checks whether `pos` is on the building's floor, which decides if its ceiling is visible
+    pub fn is_inside(&self, pos: Vec2D) -> bool {
+        hitbox_contains(&self.floor, pos)
+    }
+}
+
+/// Checks whether a building's ceiling should be hidden for a player at `player_pos`,
+/// which is the case on the building's floor and within `CEILING_HIDE_MARGIN` of it
+pub fn should_hide_ceiling(player_pos: Vec2D, building: &Building) -> bool {
+    building.is_inside(player_pos)
+        || hitboxes_collide(
+            &Hitbox::Circle(CircleHitbox::new(player_pos, CEILING_HIDE_MARGIN)),
+            &building.floor,
+        )
+}
@@ -0,0 +1,50 @@
+use crate::constants::ZIndexes;
+use crate::utils::bitstream::{Stream, StreamError};
+use crate::utils::suroi_bitstream::{SuroiBitStream, OBJECT_ID_BITS};
+use crate::utils::vectors::Vec2D;
+
+/// A position, the player's id and whether they were downed
+const SERIALIZED_BITS: usize = 2 * 16 + OBJECT_ID_BITS + 1;
+
+/// Marks the spot where a player died (or was downed)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeathMarker {
+    pub position: Vec2D,
+    /// Object id of the player who died
+    pub player_id: u32,
+    pub is_downed: bool,
+}
+
+impl DeathMarker {
+    pub fn from_death(player_id: u32, position: Vec2D, downed: bool) -> DeathMarker {
+        DeathMarker {
+            position,
+            player_id,
+            is_downed: downed,
+        }
+    }
+
+    /// The layer to render this marker on, which is below the water for markers placed in it
+    pub fn z_index(&self, underwater: bool) -> ZIndexes {
+        if underwater {
+            ZIndexes::UnderWaterDeathMarkers
+        } else {
+            ZIndexes::DeathMarkers
+        }
+    }
+
+    pub fn serialize(&self, stream: &mut SuroiBitStream) {
+        stream.write_position(self.position);
+        stream.write_object_id(self.player_id);
+        stream.write_boolean(self.is_downed);
+    }
+
+    pub fn deserialize(stream: &mut SuroiBitStream) -> Result<DeathMarker, StreamError> {
+        stream.ensure_bits(SERIALIZED_BITS)?;
+        Ok(DeathMarker {
+            position: stream.read_position(),
+            player_id: stream.read_object_id(),
+            is_downed: stream.read_boolean(),
+        })
+    }
+}
@@ -0,0 +1,26 @@
+use crate::constants::ZIndexes;
+use crate::typings::Orientation;
+use crate::utils::vectors::Vec2D;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Decal {
+    pub position: Vec2D,
+    pub orientation: Orientation,
+    pub z_index: ZIndexes,
+}
+
+impl Decal {
+    /// Creates a decal on the default decal layer
+    pub fn new(position: Vec2D, orientation: Orientation) -> Decal {
+        Decal {
+            position,
+            orientation,
+            z_index: ZIndexes::Decals,
+        }
+    }
+
+    pub fn with_z_index(mut self, z_index: ZIndexes) -> Decal {
+        self.z_index = z_index;
+        self
+    }
+}
@@ -0,0 +1,67 @@
+use crate::utils::hitbox::Hitbox;
+
+#[derive(Debug, Clone)]
+pub struct Obstacle {
+    pub hitbox: Hitbox,
+    pub health: f32,
+    pub max_health: f32,
+    pub indestructible: bool,
+    /// Loot table to spawn items from once destroyed, if any
+    pub loot_table: Option<String>,
+}
+
+impl Obstacle {
+    pub fn new(hitbox: Hitbox, max_health: f32) -> Obstacle {
+        Obstacle {
+            hitbox,
+            health: max_health,
+            max_health,
+            indestructible: false,
+            loot_table: None,
+        }
+    }
+
+    pub fn indestructible(hitbox: Hitbox) -> Obstacle {
+        Obstacle {
+            indestructible: true,
+            ..Obstacle::new(hitbox, f32::INFINITY)
+        }
+    }
+
+    pub fn with_loot_table(mut self, loot_table: &str) -> Obstacle {
+        self.loot_table = Some(loot_table.to_string());
+        self
+    }
+
+    pub fn is_destroyed(&self) -> bool {
+        !self.indestructible && self.health <= 0.0
+    }
+
+    /// Deals `amount` damage to this obstacle. Returns `true` if that destroyed it;
+    /// damaging an obstacle that's already destroyed, or an indestructible one, does nothing
+    pub fn damage(&mut self, amount: f32) -> bool {
+        if self.indestructible || self.is_destroyed() {
+            return false;
+        }
+
+        self.health = (self.health - amount).max(0.0);
+        self.is_destroyed()
+    }
+
+    /// Remaining health, from `0` to `1`
+    pub fn health_fraction(&self) -> f32 {
+        if self.indestructible {
+            return 1.0;
+        }
+        self.health / self.max_health
+    }
+
+    /// The loot table to spawn items from, once this obstacle is destroyed
+    pub fn loot_to_spawn(&self) -> Option<&str> {
+        if self.is_destroyed() {
+            self.loot_table.as_deref()
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use crate::constants::GAME_CONSTANTS;
+use crate::utils::math::{ease, numeric};
+use crate::utils::vectors::Vec2D;
+
+/// The descent of a parachute (an airdrop's or a player's) towards its landing spot
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Parachute {
+    /// Where the parachute touches the ground
+    pub landing_position: Vec2D,
+    /// Horizontal distance covered during the whole fall, e.g. because of wind
+    pub drift: Vec2D,
+    /// Altitude at the start of the fall
+    pub start_altitude: f64,
+    /// Duration of the fall, in milliseconds
+    pub fall_time: u32,
+}
+
+impl Parachute {
+    /// Creates a parachute falling straight down onto `landing_position` from an altitude
+    /// of `1`, over the airdrops' `fall_time`
+    pub fn new(landing_position: Vec2D) -> Parachute {
+        Parachute {
+            landing_position,
+            drift: Vec2D::zero(),
+            start_altitude: 1.0,
+            fall_time: GAME_CONSTANTS.airdrop.fall_time as u32,
+        }
+    }
+
+    pub fn with_drift(mut self, drift: Vec2D) -> Parachute {
+        self.drift = drift;
+        self
+    }
+
+    /// How far along the fall is, from `0` to `1`
+    pub fn progress(&self, elapsed_ms: u32) -> f64 {
+        if self.fall_time == 0 {
+            return 1.0;
+        }
+        numeric::clamp(elapsed_ms as f64 / self.fall_time as f64, 0.0, 1.0)
+    }
+
+    pub fn altitude_at(&self, elapsed_ms: u32) -> f64 {
+        self.start_altitude * (1.0 - ease::sine_in_out(self.progress(elapsed_ms)))
+    }
+
+    /// Horizontal position of the parachute, drifting linearly towards its landing position
+    pub fn position_at(&self, elapsed_ms: u32) -> Vec2D {
+        self.landing_position - self.drift * (1.0 - self.progress(elapsed_ms))
+    }
+
+    pub fn has_landed(&self, elapsed_ms: u32) -> bool {
+        elapsed_ms >= self.fall_time
+    }
+}
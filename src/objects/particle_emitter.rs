@@ -0,0 +1,57 @@
+use rand::Rng;
+
+use crate::config::CONFIG;
+use crate::utils::math::consts::TAU;
+use crate::utils::random::GameRng;
+use crate::utils::vectors::Vec2D;
+
+/// The state a synced particle starts out with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParticleState {
+    pub position: Vec2D,
+    pub velocity: Vec2D,
+    /// When the particle was spawned, in milliseconds
+    pub spawn_time: f64,
+    /// When the particle disappears, in milliseconds
+    pub expires_at: f64,
+}
+
+/// Spawns synced particles at a steady rate, flying off in random directions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParticleEmitter {
+    pub position: Vec2D,
+    /// When the emitter started emitting, in milliseconds
+    pub start_time: f64,
+    /// Particles spawned per second
+    pub rate: f64,
+    /// How long each particle lives, in milliseconds
+    pub lifetime: f64,
+    pub min_speed: f64,
+    pub max_speed: f64,
+}
+
+impl ParticleEmitter {
+    /// Number of particles emitted between `start_time` and `time`
+    fn emitted_by(&self, time: f64) -> u64 {
+        ((time - self.start_time).max(0.0) * self.rate / 1000.0).floor() as u64
+    }
+
+    /// Spawns the particles due during the tick ending at `now` (in milliseconds). Calling
+    /// this once per tick emits `rate` particles per second on average, without drifting
+    pub fn emit(&self, now: f64, rng: &mut GameRng) -> Vec<ParticleState> {
+        let tick_start = now - 1000.0 / CONFIG.tps as f64;
+        let count = self.emitted_by(now) - self.emitted_by(tick_start);
+
+        (0..count)
+            .map(|_| {
+                let speed = rng.gen_range(self.min_speed..=self.max_speed);
+                ParticleState {
+                    position: self.position,
+                    velocity: Vec2D::from_polar(rng.gen_range(0.0..TAU), Some(speed)),
+                    spawn_time: now,
+                    expires_at: now + self.lifetime,
+                }
+            })
+            .collect()
+    }
+}
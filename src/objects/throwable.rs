@@ -0,0 +1,44 @@
+use crate::config::CONFIG;
+use crate::utils::misc::drag_const;
+use crate::utils::vectors::Vec2D;
+
+/// A thrown projectile sliding along the ground, slowing down exponentially until it
+/// comes to rest at its target
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Throwable {
+    pub start: Vec2D,
+    pub velocity: Vec2D,
+    /// How quickly the projectile slows down, per second
+    pub decay_rate: f64,
+}
+
+impl Throwable {
+    /// Throws a projectile from `start` so that it lands on `target`. `aggressiveness`
+    /// is passed to `drag_const`: higher values make the projectile come to rest sooner
+    pub fn new(start: Vec2D, target: Vec2D, aggressiveness: f32) -> Throwable {
+        // drag_const gives the fraction of speed kept each tick, turn it into a per-second rate
+        let decay_rate = -(drag_const(aggressiveness, None) as f64).ln() * CONFIG.tps as f64;
+
+        Throwable {
+            start,
+            // integrating v * e^(-kt) over [0, ∞) gives v / k, which has to reach the target
+            velocity: (target - start) * decay_rate,
+            decay_rate,
+        }
+    }
+
+    /// Position of the projectile `t` seconds after the throw
+    pub fn position_at(&self, t: f64) -> Vec2D {
+        self.start + self.velocity * ((1.0 - (-self.decay_rate * t).exp()) / self.decay_rate)
+    }
+
+    /// Velocity of the projectile `t` seconds after the throw
+    pub fn velocity_at(&self, t: f64) -> Vec2D {
+        self.velocity * (-self.decay_rate * t).exp()
+    }
+
+    /// Where the projectile comes to rest
+    pub fn landing_point(&self) -> Vec2D {
+        self.start + self.velocity / self.decay_rate
+    }
+}
@@ -1,2 +1,14 @@
 pub mod vectors;
 pub mod random;
+pub mod hitbox;
+pub mod math;
+pub mod misc;
+pub mod bitstream;
+pub mod typings;
+pub mod navigation;
+pub mod objects;
+pub mod physics;
+pub mod decimal;
+pub mod smoothing;
+pub mod rate_limit;
+pub mod arena;
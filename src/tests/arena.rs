@@ -0,0 +1,40 @@
+#[cfg(test)]
+pub mod slots {
+    use crate::utils::arena::Arena;
+
+    #[test]
+    pub fn insert_get_remove() {
+        let mut arena = Arena::new();
+        let a = arena.insert("a");
+        let b = arena.insert("b");
+
+        assert_eq!(arena.len(), 2);
+        assert_eq!(arena.get(a), Some(&"a"));
+        *arena.get_mut(b).unwrap() = "c";
+        assert_eq!(arena.get(b), Some(&"c"));
+
+        assert_eq!(arena.remove(a), Some("a"));
+        assert_eq!(arena.remove(a), None);
+        assert_eq!(arena.get(a), None);
+        assert_eq!(arena.len(), 1);
+        assert!(arena.contains(b));
+    }
+
+    #[test]
+    pub fn stale_index_after_reuse() {
+        let mut arena = Arena::new();
+        let old = arena.insert(1);
+        arena.insert(2);
+        arena.remove(old);
+
+        // the freed slot is reused, but under a new generation
+        let new = arena.insert(3);
+        assert_ne!(old, new);
+        assert_eq!(arena.get(new), Some(&3));
+        assert_eq!(arena.get(old), None);
+        assert_eq!(arena.get_mut(old), None);
+        assert_eq!(arena.remove(old), None);
+        assert_eq!(arena.get(new), Some(&3));
+        assert_eq!(arena.len(), 2);
+    }
+}
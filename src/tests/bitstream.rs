@@ -0,0 +1,366 @@
+#[cfg(test)]
+pub mod suroi_stream {
+    use std::borrow::Cow;
+    use crate::typings::{CustomTeamMessage, CustomTeamPlayerInfo, GameRejectType, GameResponse};
+    use crate::constants::{ZIndexes, GAME_CONSTANTS};
+    use crate::objects::death_marker::DeathMarker;
+    use strum::{EnumCount, IntoEnumIterator};
+    use crate::utils::bitstream::{BitStream, Stream, StreamError};
+    use crate::utils::suroi_bitstream::{SuroiBitStream, ADRENALINE_BITS, GAME_REJECT_TYPE_BITS, HEALTH_BITS, TEAM_MESSAGE_TYPE_BITS, Z_INDEX_BITS};
+    use crate::utils::vectors::Vec2D;
+
+    #[test]
+    pub fn game_response_success() {
+        let mut stream = SuroiBitStream::new(16);
+        let response = GameResponse::Success { game_id: 3 };
+
+        stream.write_game_response(&response);
+        stream.set_index(0);
+        assert_eq!(Ok(response), stream.read_game_response());
+    }
+
+    #[test]
+    pub fn game_response_failure() {
+        let mut stream = SuroiBitStream::new(64);
+        let response = GameResponse::Failure {
+            message: GameRejectType::Temp,
+            reason: String::from("Teaming in solos"),
+            report_id: String::from("abc123"),
+        };
+
+        stream.write_game_response(&response);
+        stream.set_index(0);
+        assert_eq!(Ok(response), stream.read_game_response());
+    }
+
+    #[test]
+    pub fn game_response_invalid() {
+        let mut stream = SuroiBitStream::new(4);
+        stream.write_boolean(false);
+        // one past GameRejectType::Perma
+        stream.write_bits_us(3u32, GAME_REJECT_TYPE_BITS);
+        stream.set_index(0);
+        assert!(matches!(stream.read_game_response(), Err(StreamError::InvalidField { field: "game reject type", .. })));
+    }
+
+    #[test]
+    pub fn position() {
+        let mut stream = SuroiBitStream::new(4);
+        let position = Vec2D::new(812.5, 1600.0);
+
+        stream.write_position(position);
+        stream.set_index(0);
+        assert!(position.equals(stream.read_position(), Some(0.05)));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "outside of the map bounds")]
+    pub fn position_out_of_bounds() {
+        SuroiBitStream::new(4).write_position(Vec2D::new(-1.0, 100.0));
+    }
+
+    #[test]
+    pub fn health_and_adrenaline() {
+        let max_health = GAME_CONSTANTS.player.default_health as f32;
+        let max_adrenaline = GAME_CONSTANTS.player.max_adrenaline as f32;
+        let health_bound = max_health / ((1 << HEALTH_BITS) - 1) as f32 / 2.0;
+        let adrenaline_bound = max_adrenaline / ((1 << ADRENALINE_BITS) - 1) as f32 / 2.0;
+
+        for i in 0..=1000 {
+            let t = i as f32 / 1000.0;
+            let mut stream = SuroiBitStream::new(2);
+
+            stream.write_health(t * max_health);
+            stream.write_adrenaline(t * max_adrenaline);
+            stream.set_index(0);
+
+            assert!((stream.read_health() - t * max_health).abs() <= health_bound + 1e-4);
+            assert!((stream.read_adrenaline() - t * max_adrenaline).abs() <= adrenaline_bound + 1e-4);
+        }
+    }
+
+    #[test]
+    pub fn z_index() {
+        assert_eq!(Z_INDEX_BITS, 5);
+
+        let mut stream = SuroiBitStream::new(32);
+        for z_index in ZIndexes::iter() {
+            stream.write_z_index(z_index);
+        }
+        stream.set_index(0);
+        for z_index in ZIndexes::iter() {
+            assert_eq!(stream.read_z_index(), Ok(z_index));
+        }
+
+        // an index past the last layer
+        stream.set_index(0);
+        stream.write_bits_us(ZIndexes::COUNT as u32, Z_INDEX_BITS);
+        stream.set_index(0);
+        assert!(matches!(stream.read_z_index(), Err(StreamError::InvalidField { field: "z-index", .. })));
+    }
+
+    #[test]
+    pub fn death_marker() {
+        let mut stream = SuroiBitStream::new(16);
+        let markers = [
+            DeathMarker::from_death(17, Vec2D::new(400.0, 1200.0), false),
+            DeathMarker::from_death(8191, Vec2D::new(0.0, 1632.0), true),
+        ];
+
+        for marker in &markers {
+            marker.serialize(&mut stream);
+        }
+        stream.set_index(0);
+        for marker in &markers {
+            let read = DeathMarker::deserialize(&mut stream).unwrap();
+            assert!(read.position.equals(marker.position, Some(0.05)));
+            assert_eq!((read.player_id, read.is_downed), (marker.player_id, marker.is_downed));
+        }
+
+        // a marker cut short by the end of the stream
+        let mut stream = SuroiBitStream::new(4);
+        assert!(matches!(DeathMarker::deserialize(&mut stream), Err(StreamError::UnexpectedEnd { .. })));
+    }
+
+    #[test]
+    pub fn emote() {
+        let mut stream = SuroiBitStream::new(16);
+
+        stream.write_emote(12, None);
+        stream.write_emote(3, Some(Vec2D::new(816.0, 20.5)));
+        stream.set_index(0);
+
+        assert_eq!(stream.read_emote(), (12, None));
+        let (ping, position) = stream.read_emote();
+        assert_eq!(ping, 3);
+        assert!(position.unwrap().equals(Vec2D::new(816.0, 20.5), Some(0.05)));
+    }
+
+    fn round_trip_team_message(message: CustomTeamMessage) {
+        let mut stream = SuroiBitStream::new(256);
+
+        stream.write_team_message(&message);
+        stream.set_index(0);
+        assert_eq!(Ok(message), stream.read_team_message());
+    }
+
+    #[test]
+    pub fn team_message_invalid() {
+        let mut stream = SuroiBitStream::new(4);
+        // one past CustomTeamMessage::Started
+        stream.write_bits_us(6u32, TEAM_MESSAGE_TYPE_BITS);
+        stream.set_index(0);
+        assert!(matches!(
+            stream.read_team_message(),
+            Err(StreamError::InvalidField { field: "custom team message type", .. })
+        ));
+    }
+
+    fn player(optionals: bool) -> CustomTeamPlayerInfo<'static> {
+        CustomTeamPlayerInfo {
+            id: 7,
+            is_leader: optionals.then_some(true),
+            name: Cow::Borrowed("limenade"),
+            skin: Cow::Borrowed("hazel_jumpsuit"),
+            badge: optionals.then_some(Cow::Borrowed("bdg_suroi")),
+            name_color: optionals.then_some(0xff00ff),
+        }
+    }
+
+    #[test]
+    pub fn team_message_player_join() {
+        round_trip_team_message(CustomTeamMessage::PlayerJoin(player(true)));
+        round_trip_team_message(CustomTeamMessage::PlayerJoin(player(false)));
+    }
+
+    #[test]
+    pub fn team_message_player_leave() {
+        round_trip_team_message(CustomTeamMessage::PlayerLeave { id: 12, new_leader_id: Some(3) });
+        round_trip_team_message(CustomTeamMessage::PlayerLeave { id: 12, new_leader_id: None });
+    }
+
+    #[test]
+    pub fn team_message_others() {
+        round_trip_team_message(CustomTeamMessage::Join {
+            id: 1,
+            team_id: String::from("ABCD"),
+            is_leader: false,
+            auto_fill: true,
+            locked: false,
+            players: vec![player(true), player(false)],
+        });
+        round_trip_team_message(CustomTeamMessage::Settings { auto_fill: None, locked: Some(false) });
+        round_trip_team_message(CustomTeamMessage::Start);
+        round_trip_team_message(CustomTeamMessage::Started);
+    }
+
+    #[test]
+    pub fn wide_bits() {
+        let mut stream = BitStream::new(32);
+        let value_48 = 0xABCD_1234_5678u128;
+        let value_100 = (1u128 << 99) | 0x1234_5678_9ABC_DEF0_1234_5678;
+
+        stream.write_bits_wide(value_48, 48);
+        stream.write_bits_wide(value_100, 100);
+        stream.write_boolean(true);
+        assert_eq!(149, stream.get_index());
+
+        stream.set_index(0);
+        assert_eq!(value_48, stream.read_bits_wide(48));
+        assert_eq!(value_100, stream.read_bits_wide(100));
+        assert!(stream.read_boolean());
+    }
+
+    #[test]
+    pub fn wide_integers() {
+        let mut stream = BitStream::new(32);
+        stream.write_uint64(u64::MAX - 1);
+        stream.write_int128(i128::MIN + 3);
+
+        stream.set_index(0);
+        assert_eq!(u64::MAX - 1, stream.read_uint64());
+        assert_eq!(i128::MIN + 3, stream.read_int128());
+    }
+
+    #[test]
+    pub fn serialize_traits() {
+        use crate::typings::Orientation;
+        use crate::utils::bitstream::{Deserialize, Serialize};
+
+        /// How a struct composes the traits: fields are written and read in the same order
+        #[derive(Debug, PartialEq)]
+        struct Spawn {
+            position: Vec2D,
+            orientation: Orientation,
+        }
+
+        impl Serialize for Spawn {
+            fn write_to(&self, stream: &mut impl Stream) {
+                self.position.write_to(stream);
+                self.orientation.write_to(stream);
+            }
+        }
+
+        impl Deserialize for Spawn {
+            fn read_from(stream: &mut impl Stream) -> Self {
+                Spawn {
+                    position: Vec2D::read_from(stream),
+                    orientation: Orientation::read_from(stream),
+                }
+            }
+        }
+
+        let mut stream = BitStream::new(16);
+        let vector = Vec2D::new(-12.5, 300.25);
+        vector.write_to(&mut stream);
+        assert_eq!(stream.get_index(), 64);
+        stream.set_index(0);
+        assert_eq!(Vec2D::read_from(&mut stream), vector);
+
+        let spawn = Spawn { position: Vec2D::new(0.5, 1024.0), orientation: Orientation::Down };
+        stream.set_index(0);
+        spawn.write_to(&mut stream);
+        assert_eq!(stream.get_index(), 66);
+        stream.set_index(0);
+        assert_eq!(Spawn::read_from(&mut stream), spawn);
+    }
+
+    #[test]
+    pub fn protocol_version() {
+        let mut stream = SuroiBitStream::new(4);
+        stream.write_protocol_version();
+        stream.set_index(0);
+        assert_eq!(stream.read_and_check_protocol_version(), Ok(()));
+
+        let outdated = GAME_CONSTANTS.protocol_version - 1;
+        stream.set_index(0);
+        stream.write_uint16(outdated);
+        stream.set_index(0);
+        assert_eq!(
+            stream.read_and_check_protocol_version(),
+            Err(StreamError::ProtocolMismatch { expected: GAME_CONSTANTS.protocol_version, received: outdated })
+        );
+    }
+
+    #[test]
+    pub fn join_packet() {
+        use crate::typings::JoinPacket;
+
+        let packet = JoinPacket {
+            name: String::from("limenade"),
+            skin: String::from("hazel_jumpsuit"),
+            badge: Some(String::from("suroi_ogs")),
+        };
+        let mut stream = SuroiBitStream::new(64);
+        stream.write_join(&packet);
+        stream.set_index(0);
+        assert_eq!(stream.read_join(), Ok(packet));
+
+        // blank names fall back to the default one
+        let mut stream = SuroiBitStream::new(64);
+        stream.write_join(&JoinPacket { name: String::from("   "), skin: String::from("hazel_jumpsuit"), badge: None });
+        stream.set_index(0);
+        let packet = stream.read_join().unwrap();
+        assert_eq!(packet.name, GAME_CONSTANTS.player.default_name);
+        assert_eq!(packet.badge, None);
+    }
+
+    #[test]
+    pub fn join_packet_invalid() {
+        use crate::typings::JoinPacket;
+        // the name field has a fixed width, so longer names are cut to it and can't spill over
+        let max_length = GAME_CONSTANTS.player.name_max_length as usize;
+        let mut stream = SuroiBitStream::new(64);
+        stream.write_join(&JoinPacket {
+            name: "a".repeat(max_length + 1),
+            skin: String::from("hazel_jumpsuit"),
+            badge: None,
+        });
+        stream.set_index(0);
+        let packet = stream.read_join().unwrap();
+        assert_eq!(packet.name, "a".repeat(max_length));
+        assert_eq!(packet.skin, "hazel_jumpsuit");
+
+        // a control character in the name
+        let mut stream = SuroiBitStream::new(64);
+        stream.write_protocol_version();
+        stream.write_player_name("bad\tname");
+        stream.set_index(0);
+        assert!(matches!(stream.read_join(), Err(StreamError::InvalidField { field: "name", .. })));
+
+        // a skin that never ends
+        let mut stream = SuroiBitStream::new(19);
+        stream.write_protocol_version();
+        stream.write_player_name("name");
+        stream.write_ascii_string("a", Some(1));
+        stream.set_index(0);
+        assert!(matches!(stream.read_join(), Err(StreamError::InvalidField { field: "skin", .. })));
+
+        let mut stream = SuroiBitStream::new(64);
+        stream.write_uint16(GAME_CONSTANTS.protocol_version + 1);
+        stream.write_player_name("name");
+        stream.set_index(0);
+        assert_eq!(
+            stream.read_join(),
+            Err(StreamError::ProtocolMismatch {
+                expected: GAME_CONSTANTS.protocol_version,
+                received: GAME_CONSTANTS.protocol_version + 1,
+            })
+        );
+    }
+
+    #[test]
+    pub fn join_packet_truncated() {
+        // too short for even the protocol version
+        let mut stream = SuroiBitStream::new(1);
+        assert_eq!(stream.read_join(), Err(StreamError::UnexpectedEnd { needed: 16, available: 8 }));
+
+        // the name is cut off
+        let mut stream = SuroiBitStream::new(6);
+        stream.write_protocol_version();
+        stream.write_ascii_string("name", Some(4));
+        stream.set_index(0);
+        assert!(matches!(stream.read_join(), Err(StreamError::UnexpectedEnd { .. })));
+    }
+}
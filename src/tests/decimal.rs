@@ -0,0 +1,37 @@
+#[cfg(test)]
+pub mod serializers {
+    use crate::utils::decimal::{DecimalSerializer, FixedPoint};
+
+    #[test]
+    pub fn fixed_point_round_trip() {
+        let serializer = FixedPoint::new(-10.0, 10.0, 8);
+        assert_eq!(0, serializer.encode(-10.0));
+        assert_eq!(255, serializer.encode(10.0));
+        assert_eq!(255, serializer.encode(1000.0));
+        assert_eq!(-10.0, serializer.decode(0));
+        assert_eq!(10.0, serializer.decode(255));
+
+        for i in 0..=200 {
+            let value = -10.0 + i as f64 * 0.1;
+            let decoded = serializer.decode(serializer.encode(value));
+            assert!((decoded - value).abs() <= serializer.step() / 2.0 + 1e-12);
+        }
+    }
+
+    #[test]
+    pub fn fixed_point_against_float() {
+        // same width as a half-precision float, over the range of a map
+        let fixed = FixedPoint::new(0.0, 1024.0, 16);
+        let float = DecimalSerializer::new_unsigned(16, 5);
+
+        let (mut fixed_error, mut float_error) = (0.0_f64, 0.0_f64);
+        for i in 0..1000 {
+            let value = i as f64 * 1.0237;
+            fixed_error = fixed_error.max((fixed.decode(fixed.encode(value)) - value).abs());
+            float_error = float_error.max((float.decode_ieee(float.encode_ieee(value)) - value).abs());
+        }
+
+        assert!(fixed_error <= fixed.step() / 2.0 + 1e-12);
+        assert!(fixed_error < float_error);
+    }
+}
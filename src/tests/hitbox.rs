@@ -0,0 +1,633 @@
+#[cfg(test)]
+pub mod hitboxes {
+    use crate::utils::hitbox::{hitbox_area, resolve_collision_mutual, resolve_hitbox_collision, hitbox_distance, Collidable, hitboxes_collide, CapsuleHitbox, CircleHitbox, GroupHitbox, Hitbox, LineHitbox, PolygonHitbox, RectangleHitbox};
+    use crate::utils::vectors::Vec2D;
+    use crate::typings::Orientation;
+    use crate::utils::math::consts::PI;
+    use crate::utils::random::GameRng;
+    use rand::SeedableRng;
+
+    fn circle(x: f64, y: f64, radius: f64) -> Hitbox {
+        Hitbox::Circle(CircleHitbox::new(Vec2D::new(x, y), radius))
+    }
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> Hitbox {
+        Hitbox::Rect(RectangleHitbox::from_rect(width, height, Some(Vec2D::new(x, y))))
+    }
+
+    // A 2x2 square centered on (x, y)
+    fn square(x: f64, y: f64) -> Hitbox {
+        Hitbox::Polygon(PolygonHitbox::new(vec![
+            Vec2D::new(x - 1.0, y - 1.0),
+            Vec2D::new(x + 1.0, y - 1.0),
+            Vec2D::new(x + 1.0, y + 1.0),
+            Vec2D::new(x - 1.0, y + 1.0),
+        ]))
+    }
+
+    fn group(x: f64, y: f64) -> Hitbox {
+        Hitbox::Group(GroupHitbox::new(vec![circle(x - 1.0, y, 1.0), rect(x + 1.0, y, 2.0, 2.0)]))
+    }
+
+    /// Every hitbox variant, once close to the origin and once far away from it
+    fn variants() -> Vec<(Hitbox, Hitbox)> {
+        vec![
+            (circle(0.0, 0.0, 1.0), circle(100.0, 100.0, 1.0)),
+            (rect(0.0, 0.0, 2.0, 2.0), rect(100.0, 100.0, 2.0, 2.0)),
+            (square(0.0, 0.0), square(100.0, 100.0)),
+            (group(0.0, 0.0), group(100.0, 100.0)),
+        ]
+    }
+
+    #[test]
+    pub fn collide_every_pairing() {
+        for (near_a, _) in variants() {
+            for (near_b, far_b) in variants() {
+                assert!(hitboxes_collide(&near_a, &near_b), "{:?} should collide with {:?}", near_a, near_b);
+                assert!(hitboxes_collide(&near_b, &near_a), "{:?} should collide with {:?}", near_b, near_a);
+                assert!(!hitboxes_collide(&near_a, &far_b), "{:?} shouldn't collide with {:?}", near_a, far_b);
+                assert!(!hitboxes_collide(&far_b, &near_a), "{:?} shouldn't collide with {:?}", far_b, near_a);
+            }
+        }
+    }
+
+    #[test]
+    pub fn collide_polygon_inside_rect() {
+        let polygon = square(0.0, 0.0);
+        let big_rect = rect(0.0, 0.0, 10.0, 10.0);
+
+        assert!(hitboxes_collide(&polygon, &big_rect));
+        assert!(hitboxes_collide(&big_rect, &polygon));
+    }
+
+    #[test]
+    pub fn distance_every_pairing() {
+        for (near_a, far_a) in variants() {
+            for (near_b, far_b) in variants() {
+                let near = hitbox_distance(&near_a, &near_b).unwrap();
+                let near_swapped = hitbox_distance(&near_b, &near_a).unwrap();
+                assert!(near.collided && near_swapped.collided, "{:?} should collide with {:?}", near_a, near_b);
+
+                let far = hitbox_distance(&near_a, &far_b).unwrap();
+                let far_swapped = hitbox_distance(&far_a, &near_b).unwrap();
+                assert!(!far.collided && !far_swapped.collided, "{:?} shouldn't collide with {:?}", near_a, far_b);
+                assert!(far.distance > 0.0 && far_swapped.distance > 0.0);
+            }
+        }
+    }
+
+    #[test]
+    pub fn distance_polygons() {
+        // edges are 3 units apart
+        let record = hitbox_distance(&square(0.0, 0.0), &square(5.0, 0.0)).unwrap();
+        assert!(!record.collided);
+        assert_eq!(9.0, record.distance);
+
+        let record = hitbox_distance(&square(0.0, 0.0), &circle(4.0, 0.0, 1.0)).unwrap();
+        assert!(!record.collided);
+        assert_eq!(9.0 - 1.0, record.distance);
+    }
+
+    #[test]
+    pub fn distance_unsupported() {
+        let empty = Hitbox::Group(GroupHitbox::new(vec![]));
+
+        for (hitbox, _) in variants() {
+            assert!(hitbox_distance(&empty, &hitbox).is_none());
+            assert!(hitbox_distance(&hitbox, &empty).is_none());
+        }
+    }
+
+    #[test]
+    pub fn distance_group_with_polygon() {
+        let group = GroupHitbox::new(vec![square(0.0, 0.0), group(20.0, 0.0)]);
+
+        let record = group.distance_to(&circle(5.0, 0.0, 1.0)).unwrap();
+        assert!(!record.collided);
+        assert_eq!(16.0 - 1.0, record.distance);
+
+        let record = group.distance_to(&circle(1.5, 0.0, 1.0)).unwrap();
+        assert!(record.collided);
+    }
+
+    #[test]
+    pub fn transform_polygon() {
+        let polygon = PolygonHitbox::new(vec![
+            Vec2D::new(0.0, 0.0),
+            Vec2D::new(2.0, 0.0),
+            Vec2D::new(0.0, 1.0),
+        ]);
+        let pos = Vec2D::new(5.0, -3.0);
+
+        let transformed = polygon.transform(pos, Some(2.0), Some(Orientation::Right));
+        for (point, original) in transformed.points().iter().zip(polygon.points()) {
+            let expected = Vec2D::add_adjust(pos, *original, Orientation::Right) * 2.0;
+            assert!(point.equals(expected, Some(1e-9)));
+        }
+    }
+
+    #[test]
+    pub fn circle_to_polygon() {
+        fn shoelace(points: &[Vec2D]) -> f64 {
+            let len = points.len();
+            (0..len)
+                .map(|i| points[i].x * points[(i + 1) % len].y - points[(i + 1) % len].x * points[i].y)
+                .sum::<f64>()
+                .abs() / 2.0
+        }
+
+        let center = Vec2D::new(3.0, -2.0);
+        let circle = CircleHitbox::new(center, 2.0);
+        let expected = PI * 4.0;
+
+        let mut last_error = f64::MAX;
+        for segments in [3, 8, 32, 128] {
+            let polygon = circle.to_polygon(segments);
+            assert_eq!(segments, polygon.points().len());
+            assert!(polygon.is_vec_inside(center));
+
+            let error = expected - shoelace(polygon.points());
+            assert!(error > 0.0 && error < last_error);
+            last_error = error;
+        }
+        assert!(last_error < 0.01);
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn circle_to_polygon_too_few_segments() {
+        CircleHitbox::new(Vec2D::new(0.0, 0.0), 1.0).to_polygon(2);
+    }
+
+    #[test]
+    pub fn group_bounds_cache() {
+        let mut group = GroupHitbox::new(vec![circle(1.0, 1.0, 1.0), rect(4.0, 2.0, 2.0, 2.0)]);
+
+        let bounds = group.as_rectangle().corners();
+        assert_eq!((Vec2D::new(0.0, 0.0), Vec2D::new(5.0, 3.0)), (bounds[0], bounds[2]));
+        // cached value
+        assert_eq!(bounds[2], group.as_rectangle().corners()[2]);
+
+        let moved = group.transform(Vec2D::new(10.0, 10.0), None, None).as_rectangle().corners();
+        assert_eq!((Vec2D::new(10.0, 10.0), Vec2D::new(15.0, 13.0)), (moved[0], moved[2]));
+
+        group.scale(2.0);
+        let scaled = group.as_rectangle().corners();
+        assert_eq!((Vec2D::new(-1.0, -1.0), Vec2D::new(6.0, 4.0)), (scaled[0], scaled[2]));
+    }
+
+    #[test]
+    pub fn group_push_remove() {
+        let mut group = GroupHitbox::new(vec![]);
+        assert!(group.is_empty());
+
+        group.push(circle(1.0, 1.0, 1.0));
+        let bounds = group.as_rectangle().corners();
+        assert_eq!((Vec2D::new(0.0, 0.0), Vec2D::new(2.0, 2.0)), (bounds[0], bounds[2]));
+
+        // the cached bounds grow with the new member
+        group.push(rect(4.0, 2.0, 2.0, 2.0));
+        assert_eq!(group.len(), 2);
+        let bounds = group.as_rectangle().corners();
+        assert_eq!((Vec2D::new(0.0, 0.0), Vec2D::new(5.0, 3.0)), (bounds[0], bounds[2]));
+
+        assert!(group.remove(2).is_none());
+        assert!(matches!(group.remove(0), Some(Hitbox::Circle(_))));
+        assert_eq!(group.len(), 1);
+        assert!(matches!(group.children(), [Hitbox::Rect(_)]));
+        let bounds = group.as_rectangle().corners();
+        assert_eq!((Vec2D::new(3.0, 1.0), Vec2D::new(5.0, 3.0)), (bounds[0], bounds[2]));
+    }
+
+    #[test]
+    pub fn hitboxes_are_sync() {
+        // map hitboxes are shared between threads, which the group bounds cache mustn't prevent
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Hitbox>();
+        assert_send_sync::<GroupHitbox>();
+    }
+
+    #[test]
+    pub fn negative_bounds() {
+        let group = GroupHitbox::new(vec![circle(-100.0, -100.0, 5.0)]);
+        let bounds = group.as_rectangle().corners();
+        assert_eq!((Vec2D::new(-105.0, -105.0), Vec2D::new(-95.0, -95.0)), (bounds[0], bounds[2]));
+
+        let Hitbox::Polygon(polygon) = square(-100.0, -100.0) else { unreachable!() };
+        let bounds = polygon.as_rectangle().corners();
+        assert_eq!((Vec2D::new(-101.0, -101.0), Vec2D::new(-99.0, -99.0)), (bounds[0], bounds[2]));
+    }
+
+    #[test]
+    pub fn overlaps_rect() {
+        fn overlaps(hitbox: &Hitbox, min: Vec2D, max: Vec2D) -> bool {
+            match hitbox {
+                Hitbox::Circle(hitbox) => hitbox.overlaps_rect(min, max),
+                Hitbox::Rect(hitbox) => hitbox.overlaps_rect(min, max),
+                Hitbox::Polygon(hitbox) => hitbox.overlaps_rect(min, max),
+                Hitbox::Group(hitbox) => hitbox.overlaps_rect(min, max),
+                Hitbox::Line(hitbox) => hitbox.overlaps_rect(min, max),
+                Hitbox::Capsule(hitbox) => hitbox.overlaps_rect(min, max),
+            }
+        }
+
+        for (hitbox, _) in variants() {
+            // every variant spans from (-2, -1) to (2, 1) at most
+            assert!(overlaps(&hitbox, Vec2D::new(0.5, 0.5), Vec2D::new(10.0, 10.0)), "{:?} is partially inside", hitbox);
+            assert!(overlaps(&hitbox, Vec2D::new(-10.0, -10.0), Vec2D::new(10.0, 10.0)), "{:?} is fully inside", hitbox);
+            assert!(!overlaps(&hitbox, Vec2D::new(5.0, 5.0), Vec2D::new(10.0, 10.0)), "{:?} is outside", hitbox);
+        }
+
+        // inside the polygon's bounding box, but outside the triangle itself
+        let triangle = PolygonHitbox::new(vec![Vec2D::new(0.0, 0.0), Vec2D::new(4.0, 0.0), Vec2D::new(0.0, 4.0)]);
+        assert!(!triangle.overlaps_rect(Vec2D::new(3.0, 3.0), Vec2D::new(3.5, 3.5)));
+    }
+
+    #[test]
+    pub fn random_perimeter_point() {
+        let mut rng = GameRng::seed_from_u64(1525);
+
+        let circle = CircleHitbox::new(Vec2D::new(3.0, -2.0), 4.0);
+        for _ in 0..100 {
+            let point = circle.random_perimeter_point(&mut rng);
+            assert!((point.distance_to(Vec2D::new(3.0, -2.0)) - 4.0).abs() < 1e-9);
+        }
+
+        let rect = RectangleHitbox::from_line(Vec2D::new(-1.0, 0.0), Vec2D::new(5.0, 2.0));
+        let (mut horizontal, mut vertical) = (0, 0);
+        for _ in 0..1000 {
+            let point = rect.random_perimeter_point(&mut rng);
+            let near = |a: f64, b: f64| (a - b).abs() < 1e-9;
+            let on_x = near(point.x, -1.0) || near(point.x, 5.0);
+            let on_y = near(point.y, 0.0) || near(point.y, 2.0);
+            assert!(on_x || on_y, "{point:?} isn't on an edge");
+            assert!((-1.0 - 1e-9..=5.0 + 1e-9).contains(&point.x) && (-1e-9..=2.0 + 1e-9).contains(&point.y));
+
+            if on_y { horizontal += 1 } else { vertical += 1 }
+        }
+        // the horizontal edges are 3 times longer than the vertical ones
+        assert!((2..=4).contains(&(horizontal / vertical)));
+
+        let triangle = PolygonHitbox::new(vec![
+            Vec2D::new(0.0, 0.0),
+            Vec2D::new(4.0, 0.0),
+            Vec2D::new(0.0, 3.0),
+        ]);
+        for _ in 0..100 {
+            let point = triangle.random_perimeter_point(&mut rng);
+            let on_hypotenuse = (point.x / 4.0 + point.y / 3.0 - 1.0).abs() < 1e-9;
+            assert!(point.x.abs() < 1e-9 || point.y.abs() < 1e-9 || on_hypotenuse);
+        }
+    }
+
+    #[test]
+    pub fn mutual_resolution() {
+        // 2 units of penetration along the x axis
+        let overlapping = || (CircleHitbox::new(Vec2D::new(0.0, 0.0), 2.0), CircleHitbox::new(Vec2D::new(2.0, 0.0), 2.0));
+
+        let (mut a, mut b) = overlapping();
+        resolve_collision_mutual(&mut a, &mut b, 1.0, 1.0);
+        assert!(a.get_center().equals(Vec2D::new(-1.0, 0.0), None));
+        assert!(b.get_center().equals(Vec2D::new(3.0, 0.0), None));
+        assert!(!a.collides_with(&b.as_hitbox()));
+
+        let (mut a, mut b) = overlapping();
+        resolve_collision_mutual(&mut a, &mut b, 3.0, 1.0);
+        assert!(a.get_center().equals(Vec2D::new(-0.5, 0.0), None));
+        assert!(b.get_center().equals(Vec2D::new(3.5, 0.0), None));
+
+        let (mut a, mut b) = overlapping();
+        resolve_collision_mutual(&mut a, &mut b, f64::INFINITY, 1.0);
+        assert_eq!(a.get_center(), Vec2D::new(0.0, 0.0));
+        assert!(b.get_center().equals(Vec2D::new(4.0, 0.0), None));
+
+        // nothing happens when they don't touch
+        let (mut a, mut b) = (CircleHitbox::new(Vec2D::new(0.0, 0.0), 1.0), CircleHitbox::new(Vec2D::new(5.0, 0.0), 1.0));
+        resolve_collision_mutual(&mut a, &mut b, 1.0, 1.0);
+        assert_eq!((a.get_center(), b.get_center()), (Vec2D::new(0.0, 0.0), Vec2D::new(5.0, 0.0)));
+    }
+
+    #[test]
+    pub fn circle_polygon_collision() {
+        // a U shape: the notch spans x 2..4 and y 2..6
+        let u_shape = PolygonHitbox::new(vec![
+            Vec2D::new(0.0, 0.0),
+            Vec2D::new(6.0, 0.0),
+            Vec2D::new(6.0, 6.0),
+            Vec2D::new(4.0, 6.0),
+            Vec2D::new(4.0, 2.0),
+            Vec2D::new(2.0, 2.0),
+            Vec2D::new(2.0, 6.0),
+            Vec2D::new(0.0, 6.0),
+        ]);
+        let both_ways = |circle: CircleHitbox| {
+            let result = u_shape.collides_with(&circle.as_hitbox());
+            assert_eq!(result, circle.collides_with(&u_shape.as_hitbox()));
+            result
+        };
+
+        // sitting in the notch without touching its sides, even though it's inside the bounding box
+        assert!(!both_ways(CircleHitbox::new(Vec2D::new(3.0, 4.5), 0.9)));
+        // a bigger one touches the sides of the notch
+        assert!(both_ways(CircleHitbox::new(Vec2D::new(3.0, 4.5), 1.1)));
+
+        // grazing the outer bottom edge from outside
+        assert!(both_ways(CircleHitbox::new(Vec2D::new(3.0, -0.99), 1.0)));
+        assert!(!both_ways(CircleHitbox::new(Vec2D::new(3.0, -1.01), 1.0)));
+        // completely inside one of the prongs
+        assert!(both_ways(CircleHitbox::new(Vec2D::new(1.0, 4.0), 0.5)));
+    }
+
+    #[test]
+    pub fn polygon_polygon_collision() {
+        let Hitbox::Polygon(origin) = square(0.0, 0.0) else { unreachable!() };
+
+        // overlapping squares
+        assert!(origin.collides_with(&square(1.0, 1.0)));
+        assert!(origin.collides_with(&square(0.0, 0.0)));
+
+        // touching edges don't count as a collision
+        assert!(!origin.collides_with(&square(2.0, 0.0)));
+
+        // clearly separated polygons
+        let triangle = Hitbox::Polygon(PolygonHitbox::new(vec![
+            Vec2D::new(1.5, 0.0),
+            Vec2D::new(3.0, 0.0),
+            Vec2D::new(3.0, -1.5),
+        ]));
+        assert!(!origin.collides_with(&triangle));
+        assert!(!origin.collides_with(&square(10.0, -10.0)));
+        assert!(!hitboxes_collide(&square(0.0, 0.0), &triangle));
+    }
+
+    #[test]
+    pub fn area() {
+        assert!((hitbox_area(&circle(5.0, 5.0, 2.0)) - 4.0 * PI).abs() < 1e-9);
+        assert_eq!(6.0, hitbox_area(&rect(-3.0, 1.0, 2.0, 3.0)));
+        assert_eq!(4.0, hitbox_area(&square(-50.0, 20.0)));
+
+        // winding doesn't matter
+        let triangle = PolygonHitbox::new(vec![Vec2D::new(0.0, 0.0), Vec2D::new(0.0, 3.0), Vec2D::new(4.0, 0.0)]);
+        assert_eq!(6.0, triangle.area());
+
+        // the circle and rectangle of a group, overlaps included
+        assert!((hitbox_area(&group(0.0, 0.0)) - (PI + 4.0)).abs() < 1e-9);
+        let nested = GroupHitbox::new(vec![group(0.0, 0.0), square(0.0, 0.0)]);
+        assert!((nested.area() - (PI + 8.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    pub fn random_point_weighted_by_area() {
+        // 16 square units on the left, 4 on the right
+        let group = GroupHitbox::new(vec![rect(-10.0, 0.0, 4.0, 4.0), rect(10.0, 0.0, 2.0, 2.0)]);
+        let samples = 5000;
+
+        let left = (0..samples).filter(|_| group.random_point().x < 0.0).count();
+        let fraction = left as f64 / samples as f64;
+        assert!((fraction - 0.8).abs() < 0.05, "{} of the points fell in the larger child", fraction);
+    }
+
+    #[test]
+    pub fn random_point_zero_area_group() {
+        let lines = GroupHitbox::new(vec![
+            Hitbox::Line(LineHitbox::new(Vec2D::new(0.0, 0.0), Vec2D::new(10.0, 0.0))),
+            Hitbox::Line(LineHitbox::new(Vec2D::new(0.0, 5.0), Vec2D::new(10.0, 5.0))),
+        ]);
+
+        let points: Vec<Vec2D> = (0..100).map(|_| lines.random_point()).collect();
+        assert!(points.iter().all(|point| (0.0..=10.0).contains(&point.x) && (point.y == 0.0 || point.y == 5.0)));
+        // both lines get picked
+        assert!(points.iter().any(|point| point.y == 0.0) && points.iter().any(|point| point.y == 5.0));
+    }
+
+    #[test]
+    pub fn accessors() {
+        let circle = CircleHitbox::new(Vec2D::new(3.0, -4.0), 2.5);
+        assert_eq!(Vec2D::new(3.0, -4.0), circle.position());
+        assert_eq!(2.5, circle.radius());
+
+        let rect = RectangleHitbox::from_line(Vec2D::new(5.0, 1.0), Vec2D::new(-1.0, 4.0));
+        assert_eq!(Vec2D::new(-1.0, 1.0), rect.min());
+        assert_eq!(Vec2D::new(5.0, 4.0), rect.max());
+        assert_eq!(6.0, rect.width());
+        assert_eq!(3.0, rect.height());
+    }
+
+    fn line(ax: f64, ay: f64, bx: f64, by: f64) -> Hitbox {
+        Hitbox::Line(LineHitbox::new(Vec2D::new(ax, ay), Vec2D::new(bx, by)))
+    }
+
+    #[test]
+    pub fn line_collisions() {
+        let fence = line(0.0, 0.0, 10.0, 0.0);
+
+        // a circle sitting on the segment, and one just out of reach
+        assert!(hitboxes_collide(&fence, &circle(5.0, 0.5, 1.0)));
+        assert!(hitboxes_collide(&circle(5.0, 0.5, 1.0), &fence));
+        assert!(!hitboxes_collide(&fence, &circle(5.0, 1.5, 1.0)));
+        // past the end of the segment, even though it's on the same infinite line
+        assert!(!hitboxes_collide(&fence, &circle(12.0, 0.0, 1.0)));
+        assert!(hitboxes_collide(&fence, &circle(10.5, 0.0, 1.0)));
+
+        assert!(hitboxes_collide(&fence, &rect(5.0, 0.0, 2.0, 2.0)));
+        assert!(!hitboxes_collide(&rect(5.0, 5.0, 2.0, 2.0), &fence));
+        assert!(hitboxes_collide(&fence, &line(5.0, -1.0, 5.0, 1.0)));
+        assert!(!hitboxes_collide(&fence, &line(5.0, 1.0, 5.0, 2.0)));
+        assert!(hitboxes_collide(&square(0.0, 0.0), &fence));
+        assert!(hitboxes_collide(&group(5.0, 0.0), &fence));
+    }
+
+    #[test]
+    pub fn line_crossing() {
+        let Hitbox::Line(fence) = line(0.0, 0.0, 10.0, 0.0) else { unreachable!() };
+
+        let hit = fence.intersects_line(Vec2D::new(3.0, 5.0), Vec2D::new(3.0, -5.0)).unwrap();
+        assert!(hit.point.equals(Vec2D::new(3.0, 0.0), Some(1e-9)));
+        assert!(hit.normal.equals(Vec2D::new(0.0, 1.0), Some(1e-9)));
+        assert!(fence.intersects_line(Vec2D::new(11.0, 5.0), Vec2D::new(11.0, -5.0)).is_none());
+
+        let record = hitbox_distance(&line(0.0, 0.0, 10.0, 0.0), &circle(5.0, 3.0, 1.0)).unwrap();
+        assert!(!record.collided);
+        assert!((record.distance - 8.0).abs() < 1e-9);
+        assert!(hitbox_distance(&line(0.0, 0.0, 10.0, 0.0), &square(0.0, 0.0)).is_none());
+
+        assert_eq!(0.0, fence.area());
+        assert!(!fence.is_vec_inside(Vec2D::new(5.0, 0.0)));
+        let with_fence = GroupHitbox::new(vec![group(0.0, 0.0), line(0.0, 0.0, 10.0, -20.0)]);
+        assert_eq!(Vec2D::new(10.0, 1.0), with_fence.as_rectangle().corners()[2]);
+        assert_eq!(Vec2D::new(-2.0, -20.0), with_fence.as_rectangle().corners()[0]);
+    }
+
+    // A bullet travelling from (0, 0) to (10, 0) in one tick
+    fn bullet() -> CapsuleHitbox {
+        CapsuleHitbox::new(Vec2D::new(0.0, 0.0), Vec2D::new(10.0, 0.0), 0.5)
+    }
+
+    #[test]
+    pub fn capsule_collisions() {
+        let bullet = bullet();
+
+        // along the sides and past the rounded ends
+        assert!(bullet.collides_with(&circle(5.0, 1.2, 1.0)));
+        assert!(!bullet.collides_with(&circle(5.0, 1.6, 1.0)));
+        assert!(bullet.collides_with(&circle(11.4, 0.0, 1.0)));
+        assert!(!bullet.collides_with(&circle(11.0, 1.5, 1.0)));
+        assert!(hitboxes_collide(&circle(-1.2, 0.0, 1.0), &bullet.as_hitbox()));
+
+        assert!(bullet.collides_with(&rect(5.0, 0.0, 1.0, 1.0)));
+        assert!(bullet.collides_with(&rect(5.0, 0.9, 1.0, 1.0)));
+        assert!(!bullet.collides_with(&rect(5.0, 1.6, 1.0, 1.0)));
+        // the rounded corner keeps a rectangle diagonally off the end out of reach
+        assert!(!bullet.collides_with(&rect(11.0, 1.0, 1.0, 1.0)));
+        assert!(hitboxes_collide(&rect(-0.8, 0.0, 1.0, 1.0), &bullet.as_hitbox()));
+
+        assert!(bullet.collides_with(&line(5.0, 0.4, 5.0, 3.0)));
+        assert!(bullet.collides_with(&square(5.0, -1.0)));
+        assert!(!bullet.collides_with(&square(5.0, -2.0)));
+    }
+
+    #[test]
+    pub fn capsule_random_point() {
+        let bullet = bullet();
+        for _ in 0..500 {
+            let point = bullet.random_point();
+            assert!(bullet.is_vec_inside(point), "{:?} isn't inside the capsule", point);
+        }
+
+        // without a radius, points fall on the segment instead of never being found
+        let flat = CapsuleHitbox::new(Vec2D::new(0.0, 0.0), Vec2D::new(10.0, 0.0), 0.0);
+        for _ in 0..100 {
+            let point = flat.random_point();
+            assert!(point.y == 0.0 && (0.0..=10.0).contains(&point.x), "{:?} isn't on the segment", point);
+        }
+    }
+
+    #[test]
+    pub fn capsule_distance() {
+        let record = hitbox_distance(&bullet().as_hitbox(), &circle(5.0, 3.0, 1.0)).unwrap();
+        assert!(!record.collided);
+        assert!((record.distance - (9.0 - 2.25)).abs() < 1e-9);
+
+        let record = hitbox_distance(&rect(13.0, 0.0, 2.0, 2.0), &bullet().as_hitbox()).unwrap();
+        assert!(!record.collided);
+        assert!((record.distance - (4.0 - 0.25)).abs() < 1e-9);
+    }
+
+    #[test]
+    pub fn capsule_shape() {
+        let bullet = bullet();
+        assert!((bullet.area() - (10.0 + PI * 0.25)).abs() < 1e-9);
+        let bounds = bullet.as_rectangle().corners();
+        assert_eq!((Vec2D::new(-0.5, -0.5), Vec2D::new(10.5, 0.5)), (bounds[0], bounds[2]));
+
+        let hit = bullet.intersects_line(Vec2D::new(5.0, 5.0), Vec2D::new(5.0, -5.0)).unwrap();
+        assert!(hit.point.equals(Vec2D::new(5.0, 0.5), Some(1e-9)));
+        assert!(hit.normal.equals(Vec2D::new(0.0, 1.0), Some(1e-9)));
+        let hit = bullet.intersects_line(Vec2D::new(15.0, 0.0), Vec2D::new(5.0, 0.0)).unwrap();
+        assert!(hit.point.equals(Vec2D::new(10.5, 0.0), Some(1e-9)));
+
+        let mut rng = GameRng::seed_from_u64(7);
+        for _ in 0..100 {
+            let point = bullet.random_perimeter_point(&mut rng);
+            let closest_x = point.x.clamp(0.0, 10.0);
+            assert!((point.distance_to(Vec2D::new(closest_x, 0.0)) - 0.5).abs() < 1e-9);
+        }
+
+        let outline = bullet.to_polygon(16);
+        assert!(outline.points().iter().all(|point| {
+            (point.distance_to(Vec2D::new(point.x.clamp(0.0, 10.0), 0.0)) - 0.5).abs() < 1e-9
+        }));
+    }
+
+    #[test]
+    pub fn group_resolution() {
+        // a circle overlapping the group's rectangle (which spans from (0, -1) to (2, 1))
+        let mut wall = group(0.0, 0.0);
+        let mut player = circle(2.5, 0.0, 1.0);
+        resolve_hitbox_collision(&mut player, &mut wall);
+        let Hitbox::Circle(moved) = &player else { unreachable!() };
+        assert!(moved.position().equals(Vec2D::new(3.0, 0.0), Some(1e-9)));
+        assert!(!hitboxes_collide(&player, &wall));
+
+        // resolving the group itself leaves it in place and moves the circle out of its members
+        let Hitbox::Group(mut wall) = group(0.0, 0.0) else { unreachable!() };
+        let mut obstacle = circle(-2.5, 0.0, 1.0);
+        wall.resolve_collision(&mut obstacle);
+        let Hitbox::Circle(moved) = &obstacle else { unreachable!() };
+        assert!(moved.position().equals(Vec2D::new(-3.0, 0.0), Some(1e-9)));
+        assert!(!wall.collides_with(&obstacle));
+        assert_eq!(-2.0, wall.as_rectangle().corners()[0].x);
+
+        // two groups used to bounce the resolution back and forth forever
+        let Hitbox::Group(mut a) = group(0.0, 0.0) else { unreachable!() };
+        let mut b = group(1.0, 0.0);
+        assert!(a.collides_with(&b));
+        a.resolve_collision(&mut b);
+        let Hitbox::Group(pushed) = &b else { unreachable!() };
+        for child in pushed.children() {
+            assert!(!a.collides_with(child), "{:?} still overlaps {:?}", child, a);
+        }
+        assert_eq!(-2.0, a.as_rectangle().corners()[0].x);
+
+        // polygons can't be resolved, so neither side moves
+        let mut polygon = square(0.0, 0.0);
+        let mut player = circle(0.5, 0.0, 1.0);
+        resolve_hitbox_collision(&mut polygon, &mut player);
+        resolve_hitbox_collision(&mut player, &mut polygon);
+        let Hitbox::Circle(unmoved) = &player else { unreachable!() };
+        assert!(unmoved.position().equals(Vec2D::new(0.5, 0.0), None));
+    }
+
+    #[test]
+    pub fn bounding_circle() {
+        fn assert_circle(circle: CircleHitbox, center: Vec2D, radius: f64) {
+            assert!(circle.position().equals(center, Some(1e-9)), "{:?}", circle);
+            assert!((circle.radius() - radius).abs() < 1e-9, "{:?}", circle);
+        }
+
+        assert_circle(CircleHitbox::new(Vec2D::new(1.0, 2.0), 3.0).bounding_circle(), Vec2D::new(1.0, 2.0), 3.0);
+        // a 6x8 rectangle has a diagonal of 10
+        let Hitbox::Rect(rect) = rect(1.0, 1.0, 6.0, 8.0) else { unreachable!() };
+        assert_circle(rect.bounding_circle(), Vec2D::new(1.0, 1.0), 5.0);
+        let Hitbox::Polygon(polygon) = square(-3.0, 4.0) else { unreachable!() };
+        assert_circle(polygon.bounding_circle(), Vec2D::new(-3.0, 4.0), 2.0_f64.sqrt());
+        assert_circle(LineHitbox::new(Vec2D::new(0.0, 0.0), Vec2D::new(0.0, 10.0)).bounding_circle(), Vec2D::new(0.0, 5.0), 5.0);
+        assert_circle(bullet().bounding_circle(), Vec2D::new(5.0, 0.0), 5.5);
+
+        // the group spans from (-2, -1) to (2, 1)
+        let Hitbox::Group(group) = group(0.0, 0.0) else { unreachable!() };
+        assert_circle(group.bounding_circle(), Vec2D::zero(), 5.0_f64.sqrt());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    pub fn serde_round_trip() {
+        let json = r#"{"type":"rect","min":[0,0],"max":[4,4]}"#;
+        let Hitbox::Rect(rect) = serde_json::from_str(json).unwrap() else { panic!("not a rectangle") };
+        assert_eq!((rect.min(), rect.max()), (Vec2D::new(0.0, 0.0), Vec2D::new(4.0, 4.0)));
+
+        let Hitbox::Group(placed) = group(0.0, 0.0) else { unreachable!() };
+        let group = Hitbox::Group(placed.transform(Vec2D::new(3.0, -2.0), None, None));
+        let value = serde_json::to_value(&group).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "type": "group",
+                "hitboxes": [
+                    { "type": "circle", "position": [2.0, -2.0], "radius": 1.0 },
+                    { "type": "rect", "min": [3.0, -3.0], "max": [5.0, -1.0] },
+                ],
+                "position": [3.0, -2.0],
+            })
+        );
+
+        let Hitbox::Group(back) = serde_json::from_value(value.clone()).unwrap() else { panic!("not a group") };
+        assert_eq!(back.position(), Vec2D::new(3.0, -2.0));
+        let [Hitbox::Circle(circle), Hitbox::Rect(rect)] = back.children() else { panic!("{:?}", back.children()) };
+        assert_eq!((circle.position(), circle.radius()), (Vec2D::new(2.0, -2.0), 1.0));
+        assert_eq!((rect.min(), rect.max()), (Vec2D::new(3.0, -3.0), Vec2D::new(5.0, -1.0)));
+        let bounds = back.as_rectangle();
+        assert_eq!((bounds.min(), bounds.max()), (Vec2D::new(1.0, -3.0), Vec2D::new(5.0, -1.0)));
+        assert_eq!(serde_json::to_value(Hitbox::Group(back)).unwrap(), value);
+    }
+}
@@ -0,0 +1,408 @@
+#[cfg(test)]
+pub mod collisions {
+    use crate::utils::math::collisions::{check_rects, sweep_and_prune};
+    use crate::utils::vectors::Vec2D;
+
+    #[test]
+    pub fn sweep_and_prune_matches_brute_force() {
+        // a mix of overlapping, touching and isolated boxes
+        let boxes: Vec<(Vec2D, Vec2D)> = (0..40)
+            .map(|i| {
+                let i = i as f64;
+                let min = Vec2D::new((i * 7.3) % 50.0, (i * 3.1) % 20.0);
+                (min, min + Vec2D::new(1.0 + (i % 4.0) * 2.0, 2.0 + (i % 3.0)))
+            })
+            .collect();
+
+        let mut brute_force: Vec<(usize, usize)> = vec![];
+        for a in 0..boxes.len() {
+            for b in a + 1..boxes.len() {
+                if check_rects(boxes[a].0, boxes[a].1, boxes[b].0, boxes[b].1) {
+                    brute_force.push((a, b));
+                }
+            }
+        }
+
+        assert!(!brute_force.is_empty());
+        assert_eq!(brute_force, sweep_and_prune(&boxes));
+    }
+
+    #[test]
+    pub fn sweep_and_prune_separate_boxes() {
+        let boxes = [
+            (Vec2D::new(0.0, 0.0), Vec2D::new(1.0, 1.0)),
+            (Vec2D::new(1.0, 0.0), Vec2D::new(2.0, 1.0)),
+            (Vec2D::new(0.5, 5.0), Vec2D::new(1.5, 6.0)),
+        ];
+
+        assert!(sweep_and_prune(&boxes).is_empty());
+    }
+}
+
+#[cfg(test)]
+pub mod intersection {
+    use crate::utils::math::intersections::{ray_line, rect_circle, swept_circle_rect};
+    use crate::utils::vectors::Vec2D;
+
+    #[test]
+    pub fn ray_line_hit_and_miss() {
+        let start = Vec2D::new(5.0, -1.0);
+        let end = Vec2D::new(5.0, 1.0);
+
+        assert_eq!(Some(5.0), ray_line(Vec2D::new(0.0, 0.0), Vec2D::new(1.0, 0.0), start, end));
+        assert_eq!(None, ray_line(Vec2D::new(0.0, 0.0), Vec2D::new(-1.0, 0.0), start, end));
+        assert_eq!(None, ray_line(Vec2D::new(0.0, 5.0), Vec2D::new(1.0, 0.0), start, end));
+    }
+
+    #[test]
+    pub fn rect_circle_centered() {
+        let (min, max) = (Vec2D::new(-2.0, -1.0), Vec2D::new(2.0, 1.0));
+
+        // dead center: still pushed out along one axis
+        let response = rect_circle(min, max, Vec2D::new(0.0, 0.0), 0.5).unwrap();
+        assert_eq!(response.dir, Vec2D::new(0.0, -1.0));
+        assert_eq!(response.pen, 1.5);
+
+        let response = rect_circle(min, max, Vec2D::new(1.5, 0.0), 0.5).unwrap();
+        assert_eq!(response.dir, Vec2D::new(1.0, 0.0));
+        assert_eq!(response.pen, 1.0);
+    }
+
+    #[test]
+    pub fn swept_circle_rect_contact() {
+        let (min, max) = (Vec2D::new(0.0, 0.0), Vec2D::new(2.0, 2.0));
+
+        // head-on into the left side
+        let t = swept_circle_rect(Vec2D::new(-5.0, 1.0), Vec2D::new(10.0, 0.0), 1.0, min, max).unwrap();
+        assert!((t - 0.4).abs() < 1e-9);
+
+        // diagonally into the rounded corner
+        let t = swept_circle_rect(Vec2D::new(-2.0, -2.0), Vec2D::new(2.0, 2.0), 1.0, min, max).unwrap();
+        let contact = Vec2D::new(-2.0, -2.0) + Vec2D::new(2.0, 2.0) * t;
+        assert!((contact.length() - 1.0).abs() < 1e-9);
+
+        // passing through the corner square of the grown rectangle, but not the rounded corner
+        assert!(swept_circle_rect(Vec2D::new(-3.0, 1.2), Vec2D::new(6.0, -6.0), 1.0, min, max).is_none());
+        // too short to reach
+        assert!(swept_circle_rect(Vec2D::new(-5.0, 1.0), Vec2D::new(3.0, 0.0), 1.0, min, max).is_none());
+        // already overlapping
+        assert!(swept_circle_rect(Vec2D::new(1.0, 1.0), Vec2D::new(10.0, 0.0), 1.0, min, max).is_none());
+    }
+}
+
+#[cfg(test)]
+pub mod numbers {
+    use crate::utils::math::numeric::{
+        clamp, clamp_ord, gcd, get_sign, is_power_of_two, lcm, next_power_of_two, round_to, inverse_lerp, inverse_lerp_clamped, lerp, remap, smootherstep, smoothstep,
+    };
+
+    #[test]
+    pub fn get_sign_zero() {
+        assert_eq!(get_sign(2.0, false), 1);
+        assert_eq!(get_sign(-2.0, false), -1);
+        assert_eq!(get_sign(2.0, true), -1);
+        assert_eq!(get_sign(-2.0, true), 1);
+
+        assert_eq!(get_sign(0.0, false), 0);
+        assert_eq!(get_sign(-0.0, false), 0);
+        assert_eq!(get_sign(0.0, true), 0);
+        assert_eq!(get_sign(-0.0, true), 0);
+    }
+
+    #[test]
+    pub fn clamp_ordered() {
+        assert_eq!(clamp(5.0, 0.0, 10.0), 5.0);
+        assert_eq!(clamp(-5.0, 0.0, 10.0), 0.0);
+        assert_eq!(clamp(15.0, 0.0, 10.0), 10.0);
+        assert_eq!(clamp(3.0, 3.0, 3.0), 3.0);
+    }
+
+    #[test]
+    pub fn clamp_reversed_bounds() {
+        // the upper bound always wins
+        assert_eq!(clamp(5.0, 10.0, 0.0), 0.0);
+        assert_eq!(clamp(-5.0, 10.0, 0.0), 0.0);
+        assert_eq!(clamp(15.0, 10.0, 0.0), 0.0);
+    }
+
+    #[test]
+    pub fn remap_reversed_range() {
+        assert_eq!(remap(2.5, 0.0, 10.0, 100.0, 0.0), 75.0);
+        assert_eq!(remap(20.0, 0.0, 10.0, 100.0, 0.0), 0.0);
+        assert_eq!(remap(7.5, 10.0, 0.0, 0.0, 100.0), 25.0);
+    }
+
+    #[test]
+    pub fn smoothstep_edges() {
+        for step in [smoothstep, smootherstep] {
+            assert_eq!(step(2.0, 6.0, 2.0), 0.0);
+            assert_eq!(step(2.0, 6.0, 4.0), 0.5);
+            assert_eq!(step(2.0, 6.0, 6.0), 1.0);
+            // clamped outside the edges
+            assert_eq!(step(2.0, 6.0, -10.0), 0.0);
+            assert_eq!(step(2.0, 6.0, 10.0), 1.0);
+        }
+
+        assert_eq!(smoothstep(0.0, 1.0, 0.25), 0.15625);
+        assert!(smootherstep(0.0, 1.0, 0.25) < smoothstep(0.0, 1.0, 0.25));
+    }
+
+    #[test]
+    pub fn inverse_lerp_factor() {
+        assert_eq!(inverse_lerp(2.0, 6.0, 3.0), 0.25);
+        assert_eq!(inverse_lerp(6.0, 2.0, 3.0), 0.75);
+        assert_eq!(inverse_lerp(2.0, 6.0, 10.0), 2.0);
+        assert_eq!(inverse_lerp(2.0, 6.0, -2.0), -1.0);
+        assert_eq!(lerp(2.0, 6.0, inverse_lerp(2.0, 6.0, 5.0)), 5.0);
+
+        assert_eq!(inverse_lerp_clamped(2.0, 6.0, 10.0), 1.0);
+        assert_eq!(inverse_lerp_clamped(2.0, 6.0, -2.0), 0.0);
+        assert_eq!(inverse_lerp_clamped(2.0, 6.0, 3.0), 0.25);
+
+        // degenerate range
+        assert_eq!(inverse_lerp(4.0, 4.0, 4.0), 0.0);
+        assert_eq!(inverse_lerp(4.0, 4.0, 9.0), 0.0);
+    }
+
+    #[test]
+    pub fn clamp_ord_generic() {
+        assert_eq!(clamp_ord(5, 0, 10), 5);
+        assert_eq!(clamp_ord(-5, 0, 10), 0);
+        assert_eq!(clamp_ord(15_i32, 0, 10), 10);
+        assert_eq!(clamp_ord(5, 10, 0), 0);
+
+        for (value, min, max) in [(0.5, 0.25, 3.0), (0.1, 0.25, 3.0), (4.0, 0.25, 3.0), (5.0, 10.0, 0.0)] {
+            assert_eq!(clamp_ord(value, min, max), clamp(value, min, max));
+        }
+    }
+
+    #[test]
+    pub fn round_to_decimals() {
+        assert_eq!(round_to(12.34567, 2), 12.35);
+        assert_eq!(round_to(12.34567, 4), 12.3457);
+        assert_eq!(round_to(-2.5678, 1), -2.6);
+        assert_eq!(round_to(2.5, 0), 3.0);
+
+        // past the limit, nothing changes
+        assert_eq!(round_to(0.1 + 0.2, 16), 0.1 + 0.2);
+        assert_eq!(round_to(1.5, u32::MAX), 1.5);
+        assert_eq!(round_to(f64::MAX, 10), f64::MAX);
+    }
+
+    #[test]
+    pub fn gcd_and_lcm() {
+        assert_eq!(6, gcd(12, 18));
+        assert_eq!(6, gcd(18, 12));
+        assert_eq!(1, gcd(17, 5));
+        assert_eq!(5, gcd(0, 5));
+        assert_eq!(5, gcd(5, 0));
+        assert_eq!(0, gcd(0, 0));
+
+        assert_eq!(12, lcm(4, 6));
+        assert_eq!(7, lcm(7, 7));
+        assert_eq!(0, lcm(0, 5));
+        assert_eq!(u64::MAX, lcm(u64::MAX, u64::MAX));
+    }
+
+    #[test]
+    pub fn powers_of_two() {
+        assert_eq!(64, next_power_of_two(33));
+        assert_eq!(64, next_power_of_two(64));
+        assert_eq!(1, next_power_of_two(0));
+        assert_eq!(1, next_power_of_two(1));
+
+        assert!(is_power_of_two(64));
+        assert!(is_power_of_two(1));
+        assert!(!is_power_of_two(63));
+        assert!(!is_power_of_two(0));
+    }
+}
+
+#[cfg(test)]
+pub mod angles {
+    use crate::typings::Orientation;
+    use crate::utils::math::angle::{
+        deg_to_rad, lerp_angle, nearest_orientation, orientation_to_rotation, to_vector,
+    };
+    use crate::utils::vectors::Vec2D;
+
+    fn assert_angle(actual: f64, expected_degrees: f64) {
+        assert!((actual - deg_to_rad(expected_degrees)).abs() < 1e-9, "{actual} != {expected_degrees}°");
+    }
+
+    #[test]
+    pub fn lerp_angle_wraps_around() {
+        // 170° to -170° goes through 180°, not through 0°
+        assert_angle(lerp_angle(deg_to_rad(170.0), deg_to_rad(-170.0), 0.25), 175.0);
+        assert_angle(lerp_angle(deg_to_rad(170.0), deg_to_rad(-170.0), 0.75), -175.0);
+        assert_angle(lerp_angle(deg_to_rad(-170.0), deg_to_rad(170.0), 0.25), -175.0);
+        assert_angle(lerp_angle(deg_to_rad(170.0), deg_to_rad(-170.0), 1.0), -170.0);
+
+        assert_angle(lerp_angle(deg_to_rad(10.0), deg_to_rad(50.0), 0.5), 30.0);
+        assert_angle(lerp_angle(deg_to_rad(10.0), deg_to_rad(50.0), 0.0), 10.0);
+    }
+
+    #[test]
+    pub fn to_vector_round_trip() {
+        assert!(to_vector(0.0).equals(Vec2D::new(1.0, 0.0), None));
+        assert!(to_vector(deg_to_rad(90.0)).equals(Vec2D::new(0.0, 1.0), None));
+
+        for degrees in (-175..=180).step_by(5) {
+            let vector = to_vector(deg_to_rad(degrees as f64));
+            assert!((vector.length() - 1.0).abs() < 1e-12);
+            assert_angle(vector.direction(), degrees as f64);
+        }
+    }
+
+    #[test]
+    pub fn nearest_orientation_snaps() {
+        for orientation in [Orientation::Up, Orientation::Right, Orientation::Down, Orientation::Left] {
+            let rotation = orientation_to_rotation(orientation);
+            assert_eq!(nearest_orientation(rotation), orientation);
+            assert_eq!(nearest_orientation(rotation + 0.3), orientation);
+            assert_eq!(nearest_orientation(rotation - 0.3), orientation);
+        }
+
+        assert_eq!(nearest_orientation(deg_to_rad(10.0)), Orientation::Up);
+        assert_eq!(nearest_orientation(deg_to_rad(-80.0)), Orientation::Right);
+        assert_eq!(nearest_orientation(deg_to_rad(80.0)), Orientation::Left);
+        assert_eq!(nearest_orientation(deg_to_rad(-170.0)), Orientation::Down);
+        assert_eq!(nearest_orientation(deg_to_rad(190.0)), Orientation::Down);
+        assert_eq!(nearest_orientation(deg_to_rad(-350.0)), Orientation::Up);
+    }
+}
+
+#[cfg(test)]
+pub mod shapes {
+    use crate::utils::math::consts::PI;
+    use crate::utils::math::geometry::{closest_point_on_rect, polygon_centroid, Circle, Rectangle};
+    use crate::utils::vectors::Vec2D;
+
+    fn rectangle(min: (f64, f64), max: (f64, f64)) -> Rectangle {
+        Rectangle { min: Vec2D::from(min), max: Vec2D::from(max) }
+    }
+
+    #[test]
+    pub fn rectangle_contains() {
+        let rect = rectangle((0.0, 0.0), (4.0, 2.0));
+
+        assert!(rect.contains(Vec2D::new(2.0, 1.0)));
+        // edges and corners
+        assert!(rect.contains(Vec2D::new(0.0, 1.0)));
+        assert!(rect.contains(Vec2D::new(4.0, 2.0)));
+        assert!(rect.contains(Vec2D::new(2.0, 0.0)));
+
+        assert!(!rect.contains(Vec2D::new(-0.01, 1.0)));
+        assert!(!rect.contains(Vec2D::new(2.0, 2.01)));
+        assert!(!rect.contains(Vec2D::new(5.0, 5.0)));
+    }
+
+    #[test]
+    pub fn rectangle_contains_rect() {
+        let rect = rectangle((0.0, 0.0), (4.0, 2.0));
+
+        assert!(rect.contains_rect(&rectangle((1.0, 0.5), (3.0, 1.5))));
+        assert!(rect.contains_rect(&rectangle((0.0, 0.0), (4.0, 2.0))));
+        // overlapping, but sticking out
+        assert!(!rect.contains_rect(&rectangle((3.0, 1.0), (5.0, 1.5))));
+        assert!(!rect.contains_rect(&rectangle((10.0, 10.0), (11.0, 11.0))));
+        // the bigger one contains the smaller one, not the other way around
+        assert!(!rectangle((1.0, 0.5), (3.0, 1.5)).contains_rect(&rect));
+    }
+
+    #[test]
+    pub fn centroid() {
+        // the centroid of a triangle is the average of its vertices
+        let triangle = [Vec2D::new(0.0, 0.0), Vec2D::new(6.0, 0.0), Vec2D::new(0.0, 3.0)];
+        assert!(polygon_centroid(&triangle).equals(Vec2D::new(2.0, 1.0), None));
+
+        // asymmetric quad: a 4x2 rectangle with a triangle on its right side, making it
+        // heavier on the left than its bounding box's center
+        let quad = [
+            Vec2D::new(0.0, 0.0),
+            Vec2D::new(4.0, 0.0),
+            Vec2D::new(10.0, 2.0),
+            Vec2D::new(0.0, 2.0),
+        ];
+        // rectangle: area 8 at (2, 1); triangle (4,0)-(10,2)-(4,2): area 6 at (6, 4/3)
+        let expected = (Vec2D::new(2.0, 1.0) * 8.0 + Vec2D::new(6.0, 4.0 / 3.0) * 6.0) / 14.0;
+        assert!(polygon_centroid(&quad).equals(expected, Some(1e-9)));
+        // winding order doesn't matter
+        let reversed: Vec<Vec2D> = quad.iter().rev().copied().collect();
+        assert!(polygon_centroid(&reversed).equals(expected, Some(1e-9)));
+
+        // degenerate: every point on a line
+        let line = [Vec2D::new(0.0, 0.0), Vec2D::new(1.0, 1.0), Vec2D::new(5.0, 5.0)];
+        assert!(polygon_centroid(&line).equals(Vec2D::new(2.0, 2.0), None));
+    }
+
+    #[test]
+    pub fn closest_point_on_rectangle() {
+        let (min, max) = (Vec2D::new(0.0, 0.0), Vec2D::new(4.0, 2.0));
+
+        // inside: the point itself
+        assert_eq!(closest_point_on_rect(min, max, Vec2D::new(1.0, 1.5)), Vec2D::new(1.0, 1.5));
+        // beside an edge: projected straight onto it
+        assert_eq!(closest_point_on_rect(min, max, Vec2D::new(2.0, -3.0)), Vec2D::new(2.0, 0.0));
+        assert_eq!(closest_point_on_rect(min, max, Vec2D::new(7.0, 1.0)), Vec2D::new(4.0, 1.0));
+        // diagonally away: the nearest corner
+        assert_eq!(closest_point_on_rect(min, max, Vec2D::new(-1.0, -1.0)), min);
+        assert_eq!(closest_point_on_rect(min, max, Vec2D::new(9.0, 5.0)), max);
+    }
+
+    #[test]
+    pub fn rectangle_inflate() {
+        let mut rect = rectangle((0.0, 0.0), (4.0, 2.0));
+        rect.inflate(1.5);
+        assert_eq!((rect.min, rect.max), (Vec2D::new(-1.5, -1.5), Vec2D::new(5.5, 3.5)));
+        assert_eq!(rect.max - rect.min, Vec2D::new(4.0 + 3.0, 2.0 + 3.0));
+
+        rect.inflate(-1.5).translate(Vec2D::new(1.0, 1.0));
+        assert_eq!((rect.min, rect.max), (Vec2D::new(1.0, 1.0), Vec2D::new(5.0, 3.0)));
+
+        // shrinking by more than half the height collapses it onto the center line
+        rect.inflate(-1.5);
+        assert_eq!((rect.min, rect.max), (Vec2D::new(2.5, 2.0), Vec2D::new(3.5, 2.0)));
+    }
+
+    #[test]
+    pub fn area_and_perimeter() {
+        let rect = rectangle((-1.0, 2.0), (3.0, 5.0));
+        assert_eq!(rect.area(), 12.0);
+        assert_eq!(rect.perimeter(), 14.0);
+
+        let point = rectangle((2.0, 2.0), (2.0, 2.0));
+        assert_eq!(point.area(), 0.0);
+        assert_eq!(point.perimeter(), 0.0);
+
+        let circle = Circle { center: Vec2D::new(5.0, 5.0), radius: 2.0 };
+        assert!((circle.area() - 4.0 * PI).abs() < 1e-9);
+        assert!((circle.circumference() - 4.0 * PI).abs() < 1e-9);
+    }
+
+    #[test]
+    pub fn intersection_and_union() {
+        fn corners(rect: &Rectangle) -> (Vec2D, Vec2D) {
+            (rect.min, rect.max)
+        }
+
+        let rect = rectangle((0.0, 0.0), (4.0, 2.0));
+
+        let overlapping = rectangle((3.0, 1.0), (5.0, 3.0));
+        assert_eq!(corners(&rect.intersection(&overlapping).unwrap()), (Vec2D::new(3.0, 1.0), Vec2D::new(4.0, 2.0)));
+        assert_eq!(corners(&rect.union(&overlapping)), (Vec2D::new(0.0, 0.0), Vec2D::new(5.0, 3.0)));
+
+        // containment: the intersection is the inner one, the union the outer one
+        let inner = rectangle((1.0, 0.5), (3.0, 1.5));
+        assert_eq!(corners(&rect.intersection(&inner).unwrap()), corners(&inner));
+        assert_eq!(corners(&inner.union(&rect)), corners(&rect));
+
+        let disjoint = rectangle((10.0, 10.0), (11.0, 12.0));
+        assert!(rect.intersection(&disjoint).is_none());
+        assert_eq!(corners(&rect.union(&disjoint)), (Vec2D::new(0.0, 0.0), Vec2D::new(11.0, 12.0)));
+
+        // sharing an edge or a corner isn't overlapping
+        assert!(rect.intersection(&rectangle((4.0, 0.0), (6.0, 2.0))).is_none());
+        assert!(rect.intersection(&rectangle((4.0, 2.0), (5.0, 3.0))).is_none());
+    }
+}
@@ -0,0 +1,21 @@
+#[cfg(test)]
+pub mod misc_utils {
+    use crate::utils::misc::bits_for;
+    use crate::utils::suroi_bitstream::OBJECT_CATEGORY_BITS;
+
+    #[test]
+    pub fn bits_for_counts() {
+        assert_eq!(0, bits_for(0));
+        assert_eq!(0, bits_for(1));
+        assert_eq!(1, bits_for(2));
+        assert_eq!(2, bits_for(3));
+        assert_eq!(4, bits_for(9));
+        assert_eq!(4, bits_for(16));
+        assert_eq!(5, bits_for(17));
+    }
+
+    #[test]
+    pub fn object_category_bits() {
+        assert_eq!(4, OBJECT_CATEGORY_BITS);
+    }
+}
@@ -0,0 +1,209 @@
+#[cfg(test)]
+pub mod pathfinding {
+    use crate::utils::hitbox::{CircleHitbox, Collidable, Hitbox, RectangleHitbox};
+    use crate::utils::math::intersections::line_rect_test;
+    use crate::utils::math::geometry::point_in_polygon;
+    use crate::utils::navigation::{
+        astar, build_nav_grid, line_of_sight, smooth_path, visibility_polygon, InfluenceMap,
+    };
+    use crate::utils::vectors::Vec2D;
+
+    #[test]
+    pub fn influence_map() {
+        let mut map = InfluenceMap::new(10.0);
+        map.add(Vec2D::new(5.0, 5.0), Vec2D::new(4.0, 0.0));
+        map.add(Vec2D::new(7.0, 2.0), Vec2D::new(0.0, 3.0));
+
+        let influence = map.get((0, 0)).unwrap();
+        assert_eq!(influence.vector, Vec2D::new(4.0, 3.0));
+        assert_eq!(influence.magnitude, 7.0);
+
+        // at the cell's center, the sample is the cell's own vector
+        assert!(map.sample(Vec2D::new(5.0, 5.0)).equals(Vec2D::new(4.0, 3.0), None));
+        // halfway to the next cell's center on one axis, a quarter of the way on the other
+        assert!(map.sample(Vec2D::new(10.0, 7.5)).equals(Vec2D::new(1.5, 1.125), None));
+        // far away, nothing is sampled
+        assert_eq!(map.sample(Vec2D::new(50.0, 50.0)), Vec2D::new(0.0, 0.0));
+
+        map.decay(0.5);
+        assert!(map.sample(Vec2D::new(5.0, 5.0)).equals(Vec2D::new(2.0, 1.5), None));
+
+        map.decay(0.0);
+        assert!(map.get((0, 0)).is_none());
+    }
+
+    #[test]
+    pub fn nav_grid() {
+        let bounds = RectangleHitbox::from_line(Vec2D::zero(), Vec2D::new(50.0, 40.0));
+        // covers cells 1..=2 on the x axis and cell 2 on the y axis
+        let wall = RectangleHitbox::from_line(Vec2D::new(12.0, 22.0), Vec2D::new(28.0, 28.0)).as_hitbox();
+
+        let grid = build_nav_grid(&[wall], &bounds, 10.0);
+        assert_eq!(grid.len(), 5);
+
+        for (x, column) in grid.iter().enumerate() {
+            assert_eq!(column.len(), 4);
+            for (y, &blocked) in column.iter().enumerate() {
+                assert_eq!(blocked, (1..=2).contains(&x) && y == 2, "cell ({x}, {y})");
+            }
+        }
+
+        assert!(build_nav_grid(&[] as &[Hitbox], &bounds, 10.0)
+            .iter()
+            .flatten()
+            .all(|&blocked| !blocked));
+    }
+
+    /// Parses rows of `.` (open) and `#` (blocked) into a grid indexed as `grid[x][y]`
+    fn parse_grid(rows: &[&str]) -> Vec<Vec<bool>> {
+        (0..rows[0].len())
+            .map(|x| rows.iter().map(|row| row.as_bytes()[x] == b'#').collect())
+            .collect()
+    }
+
+    fn assert_valid_path(grid: &[Vec<bool>], path: &[(i32, i32)]) {
+        for pair in path.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            assert!((a.0 - b.0).abs() <= 1 && (a.1 - b.1).abs() <= 1);
+            assert!(!grid[b.0 as usize][b.1 as usize]);
+            // no corner cutting
+            assert!(!grid[b.0 as usize][a.1 as usize] && !grid[a.0 as usize][b.1 as usize]);
+        }
+    }
+
+    #[test]
+    pub fn astar_straight() {
+        let grid = parse_grid(&["......"]);
+        let path = astar(&grid, (0, 0), (5, 0)).unwrap();
+        assert_eq!(path, (0..6).map(|x| (x, 0)).collect::<Vec<_>>());
+
+        assert_eq!(astar(&grid, (2, 0), (2, 0)), Some(vec![(2, 0)]));
+    }
+
+    #[test]
+    pub fn astar_around_obstacle() {
+        let grid = parse_grid(&[
+            ".....",
+            ".###.",
+            ".....",
+        ]);
+
+        let path = astar(&grid, (0, 1), (4, 1)).unwrap();
+        assert_eq!(path.first(), Some(&(0, 1)));
+        assert_eq!(path.last(), Some(&(4, 1)));
+        assert_valid_path(&grid, &path);
+        // up, along the top row and back down; cutting diagonally into row 0 would clip the wall
+        assert_eq!(path.len(), 7);
+    }
+
+    #[test]
+    pub fn astar_unreachable() {
+        let grid = parse_grid(&[
+            "..#..",
+            "..#..",
+            "..#..",
+        ]);
+        assert_eq!(astar(&grid, (0, 0), (4, 2)), None);
+
+        // diagonal gaps can't be squeezed through either
+        let grid = parse_grid(&[
+            ".#",
+            "#.",
+        ]);
+        assert_eq!(astar(&grid, (0, 0), (1, 1)), None);
+
+        assert_eq!(astar(&grid, (0, 0), (5, 5)), None);
+    }
+
+    #[test]
+    pub fn smooth_path_open_corridor() {
+        let path: Vec<Vec2D> = (0..10).map(|x| Vec2D::new(x as f64, (x % 2) as f64 * 0.5)).collect();
+        assert_eq!(smooth_path(&path, |_, _| true), vec![path[0], path[9]]);
+
+        assert_eq!(smooth_path(&path[..2], |_, _| false), path[..2].to_vec());
+        assert!(smooth_path(&[], |_, _| true).is_empty());
+    }
+
+    #[test]
+    pub fn smooth_path_around_wall() {
+        let (wall_min, wall_max) = (Vec2D::new(2.0, -1.0), Vec2D::new(3.0, 2.0));
+        let is_clear = |a: Vec2D, b: Vec2D| !line_rect_test(a, b, wall_min, wall_max);
+
+        // an L-shaped detour around the bottom of the wall
+        let path = vec![
+            Vec2D::new(0.0, 0.0),
+            Vec2D::new(0.0, 1.0),
+            Vec2D::new(0.0, 2.5),
+            Vec2D::new(1.0, 2.5),
+            Vec2D::new(2.5, 2.5),
+            Vec2D::new(4.0, 2.5),
+            Vec2D::new(5.0, 0.0),
+        ];
+
+        let smoothed = smooth_path(&path, is_clear);
+        assert_eq!(smoothed.first(), path.first());
+        assert_eq!(smoothed.last(), path.last());
+        assert!(smoothed.len() < path.len());
+        assert!(smoothed.windows(2).all(|pair| is_clear(pair[0], pair[1])));
+    }
+
+    #[test]
+    pub fn line_of_sight_clear_and_blocked() {
+        let wall = RectangleHitbox::from_line(Vec2D::new(4.0, -2.0), Vec2D::new(6.0, 2.0)).as_hitbox();
+        let obstacles = [wall];
+
+        assert!(line_of_sight(Vec2D::new(0.0, 5.0), Vec2D::new(10.0, 5.0), &obstacles));
+        assert!(!line_of_sight(Vec2D::new(0.0, 0.0), Vec2D::new(10.0, 0.0), &obstacles));
+        // stops short of the wall
+        assert!(line_of_sight(Vec2D::new(0.0, 0.0), Vec2D::new(3.0, 0.0), &obstacles));
+        assert!(line_of_sight(Vec2D::new(0.0, 0.0), Vec2D::new(10.0, 0.0), &[]));
+    }
+
+    #[test]
+    pub fn line_of_sight_grazing_circle() {
+        let obstacles = [Hitbox::Circle(CircleHitbox::new(Vec2D::new(5.0, 1.0), 1.0))];
+
+        // exactly tangent to the bottom of the circle
+        assert!(!line_of_sight(Vec2D::new(0.0, 0.0), Vec2D::new(10.0, 0.0), &obstacles));
+        // just below it
+        assert!(line_of_sight(Vec2D::new(0.0, -0.01), Vec2D::new(10.0, -0.01), &obstacles));
+        // straight through it
+        assert!(!line_of_sight(Vec2D::new(0.0, 1.0), Vec2D::new(10.0, 1.0), &obstacles));
+    }
+
+    #[test]
+    pub fn visibility_pillar_shadow() {
+        let wall = |min: (f64, f64), max: (f64, f64)| {
+            RectangleHitbox::from_line(Vec2D::from(min), Vec2D::from(max)).as_hitbox()
+        };
+        let obstacles = [
+            // a 60x60 room centered on the origin
+            wall((-31.0, -31.0), (31.0, -30.0)),
+            wall((-31.0, 30.0), (31.0, 31.0)),
+            wall((-31.0, -30.0), (-30.0, 30.0)),
+            wall((30.0, -30.0), (31.0, 30.0)),
+            // the pillar, right of the origin
+            wall((10.0, -2.0), (14.0, 2.0)),
+        ];
+
+        let origin = Vec2D::zero();
+        let visible = visibility_polygon(origin, &obstacles, 100.0);
+
+        // rays stop at the pillar's face and at the walls
+        let closest = visible.iter().map(|point| point.length()).fold(f64::MAX, f64::min);
+        assert!((closest - 10.0).abs() < 1e-6);
+        assert!(visible.iter().all(|point| point.x.abs() <= 30.0 + 1e-6 && point.y.abs() <= 30.0 + 1e-6));
+
+        // in front of the pillar and beside its shadow
+        assert!(point_in_polygon(Vec2D::new(8.0, 0.0), &visible));
+        assert!(point_in_polygon(Vec2D::new(20.0, 10.0), &visible));
+        assert!(point_in_polygon(Vec2D::new(-20.0, 0.0), &visible));
+        // inside the shadow wedge
+        assert!(!point_in_polygon(Vec2D::new(20.0, 0.0), &visible));
+        assert!(!point_in_polygon(Vec2D::new(28.0, 3.0), &visible));
+
+        // with a short range, the pillar is out of sight
+        let short = visibility_polygon(origin, &obstacles, 5.0);
+        assert!(short.iter().all(|point| (point.length() - 5.0).abs() < 1e-6));
+    }
+}
@@ -0,0 +1,210 @@
+#[cfg(test)]
+pub mod game_objects {
+    use crate::constants::{ZIndexes, GAME_CONSTANTS};
+    use strum::EnumCount;
+    use crate::config::CONFIG;
+    use crate::objects::building::{should_hide_ceiling, Building, CEILING_HIDE_MARGIN};
+    use crate::objects::death_marker::DeathMarker;
+    use crate::objects::decal::Decal;
+    use crate::objects::obstacle::Obstacle;
+    use crate::objects::parachute::Parachute;
+    use crate::objects::particle_emitter::ParticleEmitter;
+    use crate::objects::throwable::Throwable;
+    use crate::typings::Orientation;
+    use crate::utils::hitbox::{Collidable, RectangleHitbox};
+    use crate::utils::random::GameRng;
+    use crate::utils::vectors::Vec2D;
+    use rand::SeedableRng;
+
+    #[test]
+    pub fn throwable_trajectory() {
+        let start = Vec2D::new(10.0, 20.0);
+        let target = Vec2D::new(40.0, -20.0);
+        let throwable = Throwable::new(start, target, 3.0);
+
+        assert_eq!(throwable.position_at(0.0), start);
+        assert!(throwable.landing_point().equals(target, None));
+        assert!(throwable.position_at(60.0).equals(target, None));
+
+        // it keeps getting closer while slowing down
+        let mut last_distance = f64::MAX;
+        let mut last_speed = f64::MAX;
+        for i in 0..20 {
+            let t = i as f64 * 0.1;
+            let distance = throwable.position_at(t).distance_to(target);
+            let speed = throwable.velocity_at(t).length();
+            assert!(distance < last_distance && speed < last_speed);
+            (last_distance, last_speed) = (distance, speed);
+        }
+
+        // more drag settles sooner, but still lands on the target
+        let draggy = Throwable::new(start, target, 6.0);
+        assert!(draggy.position_at(0.5).distance_to(target) < throwable.position_at(0.5).distance_to(target));
+        assert!(draggy.landing_point().equals(target, None));
+    }
+
+    #[test]
+    pub fn parachute_descent() {
+        let landing = Vec2D::new(100.0, 200.0);
+        let parachute = Parachute::new(landing).with_drift(Vec2D::new(10.0, 0.0));
+        let fall_time = GAME_CONSTANTS.airdrop.fall_time as u32;
+
+        assert_eq!(parachute.altitude_at(0), 1.0);
+        assert!(parachute.altitude_at(fall_time).abs() < 1e-12);
+        assert!(parachute.altitude_at(fall_time * 2).abs() < 1e-12);
+
+        let mut last = f64::MAX;
+        for elapsed in (0..=fall_time).step_by(100) {
+            let altitude = parachute.altitude_at(elapsed);
+            assert!(altitude < last);
+            last = altitude;
+        }
+
+        assert_eq!(parachute.position_at(0), Vec2D::new(90.0, 200.0));
+        assert_eq!(parachute.position_at(fall_time / 2), Vec2D::new(95.0, 200.0));
+        assert_eq!(parachute.position_at(fall_time), landing);
+        assert!(!parachute.has_landed(fall_time - 1) && parachute.has_landed(fall_time));
+    }
+
+    #[test]
+    pub fn particle_emitter_rate() {
+        let mut rng = GameRng::seed_from_u64(1529);
+        let emitter = ParticleEmitter {
+            position: Vec2D::new(5.0, 5.0),
+            start_time: 1000.0,
+            rate: 15.0,
+            lifetime: 500.0,
+            min_speed: 2.0,
+            max_speed: 3.0,
+        };
+
+        let tick = 1000.0 / CONFIG.tps as f64;
+        // 4 seconds' worth of ticks, starting before the emitter does
+        let particles: Vec<_> = (0..=(5 * CONFIG.tps))
+            .flat_map(|i| emitter.emit(i as f64 * tick, &mut rng))
+            .collect();
+
+        assert_eq!(particles.len(), 60);
+        for particle in &particles {
+            assert_eq!(particle.position, emitter.position);
+            assert!((2.0 - 1e-9..=3.0 + 1e-9).contains(&particle.velocity.length()));
+            assert!(particle.spawn_time >= emitter.start_time);
+            assert_eq!(particle.expires_at - particle.spawn_time, 500.0);
+        }
+    }
+
+    #[test]
+    pub fn decal_render_order() {
+        let decal = |z_index| Decal::new(Vec2D::zero(), Orientation::Up).with_z_index(z_index);
+        let mut decals = [
+            decal(ZIndexes::Gas),
+            decal(ZIndexes::Players),
+            Decal::new(Vec2D::new(1.0, 1.0), Orientation::Left),
+            decal(ZIndexes::Ground),
+            decal(ZIndexes::BuildingsCeiling),
+            decal(ZIndexes::UnderWaterDeathMarkers),
+        ];
+
+        decals.sort_by(|a, b| a.z_index.render_order(&b.z_index));
+        let order: Vec<ZIndexes> = decals.iter().map(|decal| decal.z_index).collect();
+        assert_eq!(order, [
+            ZIndexes::Ground,
+            ZIndexes::UnderWaterDeathMarkers,
+            ZIndexes::Decals,
+            ZIndexes::Players,
+            ZIndexes::BuildingsCeiling,
+            ZIndexes::Gas,
+        ]);
+
+        assert_eq!(ZIndexes::Gas.as_u8(), 25);
+        for z_index in [ZIndexes::Ground, ZIndexes::Decals, ZIndexes::Gas] {
+            assert_eq!(ZIndexes::from_u8(z_index.as_u8()), Some(z_index));
+        }
+        assert_eq!(ZIndexes::from_u8(26), None);
+    }
+
+    #[test]
+    pub fn z_index_ordering() {
+        assert!(ZIndexes::Ground < ZIndexes::Players);
+        assert!(ZIndexes::Players < ZIndexes::Gas);
+        assert!(ZIndexes::UnderwaterPlayers < ZIndexes::BuildingsFloor);
+
+        for index in 0..ZIndexes::COUNT {
+            assert_eq!(ZIndexes::try_from_index(index).unwrap().as_index(), index);
+        }
+        assert_eq!(ZIndexes::try_from_index(ZIndexes::COUNT), None);
+    }
+
+    #[test]
+    pub fn death_marker_z_index() {
+        let marker = DeathMarker::from_death(3, Vec2D::new(10.0, 10.0), true);
+        assert_eq!(marker.z_index(false), ZIndexes::DeathMarkers);
+        assert_eq!(marker.z_index(true), ZIndexes::UnderWaterDeathMarkers);
+        assert!(marker.z_index(true) < marker.z_index(false));
+    }
+
+    /// A 20x10 building with 1 unit thick walls, whose top-left corner is at the origin
+    fn building() -> Building {
+        Building::new(
+            RectangleHitbox::from_line(Vec2D::zero(), Vec2D::new(20.0, 10.0)).as_hitbox(),
+            RectangleHitbox::from_line(Vec2D::new(1.0, 1.0), Vec2D::new(19.0, 9.0)).as_hitbox(),
+        )
+    }
+
+    #[test]
+    pub fn building_floor() {
+        let building = building();
+
+        assert!(building.is_inside(Vec2D::new(10.0, 5.0)));
+        assert!(building.is_inside(Vec2D::new(1.1, 8.9)));
+        assert!(!building.is_inside(Vec2D::new(0.9, 5.0)));
+        assert!(!building.is_inside(Vec2D::new(10.0, 9.1)));
+        assert!(!building.is_inside(Vec2D::new(30.0, 5.0)));
+    }
+
+    #[test]
+    pub fn ceiling_visibility() {
+        let building = building();
+
+        assert!(should_hide_ceiling(Vec2D::new(10.0, 5.0), &building));
+        // outside the floor, but close enough to the entrance
+        assert!(should_hide_ceiling(Vec2D::new(10.0, 9.0 + CEILING_HIDE_MARGIN * 0.5), &building));
+        assert!(should_hide_ceiling(Vec2D::new(1.0 - CEILING_HIDE_MARGIN * 0.9, 5.0), &building));
+        // just past the margin
+        assert!(!should_hide_ceiling(Vec2D::new(10.0, 9.0 + CEILING_HIDE_MARGIN + 0.1), &building));
+        assert!(!should_hide_ceiling(Vec2D::new(-5.0, -5.0), &building));
+    }
+
+    #[test]
+    pub fn obstacle_destruction() {
+        let hitbox = RectangleHitbox::from_rect(2.0, 2.0, None).as_hitbox();
+        let mut crate_ = Obstacle::new(hitbox.clone(), 100.0).with_loot_table("regular_crate");
+
+        assert!(!crate_.damage(30.0));
+        assert_eq!(crate_.health_fraction(), 0.7);
+        assert_eq!(crate_.loot_to_spawn(), None);
+
+        assert!(!crate_.damage(69.0));
+        assert!(crate_.damage(50.0));
+        assert!(crate_.is_destroyed());
+        assert_eq!(crate_.health_fraction(), 0.0);
+        assert_eq!(crate_.loot_to_spawn(), Some("regular_crate"));
+        // only the hit that destroys it counts
+        assert!(!crate_.damage(10.0));
+
+        let mut rock = Obstacle::new(hitbox.clone(), 50.0);
+        assert!(rock.damage(50.0));
+        assert_eq!(rock.loot_to_spawn(), None);
+    }
+
+    #[test]
+    pub fn obstacle_indestructible() {
+        let mut wall = Obstacle::indestructible(RectangleHitbox::from_rect(2.0, 2.0, None).as_hitbox());
+
+        for _ in 0..100 {
+            assert!(!wall.damage(1e9));
+        }
+        assert!(!wall.is_destroyed());
+        assert_eq!(wall.health_fraction(), 1.0);
+    }
+}
@@ -0,0 +1,150 @@
+#[cfg(test)]
+pub mod dynamics {
+    use crate::utils::misc::drag_const;
+    use crate::config::CONFIG;
+    use crate::utils::hitbox::{Hitbox, RectangleHitbox};
+    use crate::utils::physics::{
+        apply_drag, apply_drag_scalar, integrate_movement, move_with_collision, resolve_velocities, swept_move,
+    };
+    use crate::utils::vectors::Vec2D;
+
+    #[test]
+    pub fn elastic_head_on() {
+        let (v_a, v_b) = resolve_velocities(
+            Vec2D::new(3.0, 0.0),
+            Vec2D::new(-1.0, 0.0),
+            Vec2D::new(0.0, 0.0),
+            Vec2D::new(2.0, 0.0),
+            1.0,
+            1.0,
+            1.0,
+        );
+
+        // equal masses swap velocities
+        assert!(v_a.equals(Vec2D::new(-1.0, 0.0), None));
+        assert!(v_b.equals(Vec2D::new(3.0, 0.0), None));
+    }
+
+    #[test]
+    pub fn perfectly_inelastic() {
+        let (v_a, v_b) = resolve_velocities(
+            Vec2D::new(4.0, 1.0),
+            Vec2D::new(0.0, 0.0),
+            Vec2D::new(0.0, 0.0),
+            Vec2D::new(2.0, 0.0),
+            3.0,
+            1.0,
+            0.0,
+        );
+
+        // same speed along the normal, momentum conserved, tangential motion untouched
+        assert!((v_a.x - v_b.x).abs() < 1e-9);
+        assert!((v_a.x * 3.0 + v_b.x - 12.0).abs() < 1e-9);
+        assert_eq!((v_a.y, v_b.y), (1.0, 0.0));
+    }
+
+    #[test]
+    pub fn separating() {
+        let (v_a, v_b) = (Vec2D::new(-1.0, 0.0), Vec2D::new(1.0, 0.0));
+        let result = resolve_velocities(v_a, v_b, Vec2D::new(0.0, 0.0), Vec2D::new(2.0, 0.0), 1.0, 1.0, 1.0);
+        assert_eq!(result, (v_a, v_b));
+    }
+
+    #[test]
+    pub fn drag_decays() {
+        let drag = drag_const(3.0, None);
+        let mut velocity = Vec2D::new(6.0, -8.0);
+        let mut speed = 10.0;
+
+        for _ in 0..1000 {
+            let next = apply_drag(velocity, drag);
+            assert!(next.length() < velocity.length());
+            // same direction, no overshoot
+            assert!(next.x >= 0.0 && next.y <= 0.0);
+            velocity = next;
+
+            let next_speed = apply_drag_scalar(speed, drag);
+            assert!((0.0..speed).contains(&next_speed));
+            speed = next_speed;
+        }
+        assert!(velocity.length() < 1e-6 && speed < 1e-6);
+
+        // out-of-range factors are clamped
+        assert_eq!(apply_drag(Vec2D::new(1.0, 1.0), -0.5), Vec2D::zero());
+        assert_eq!(apply_drag_scalar(2.0, 1.5), 2.0);
+    }
+
+    #[test]
+    pub fn movement_speed() {
+        let start = Vec2D::new(100.0, 100.0);
+        let speed = CONFIG.movement_speed as f64;
+        let dt = 1000.0 / CONFIG.tps as f64;
+        let step = speed * dt;
+
+        let right = integrate_movement(start, Vec2D::new(1.0, 0.0), speed, dt);
+        assert!(right.equals(start + Vec2D::new(step, 0.0), Some(1e-9)));
+        let up = integrate_movement(start, Vec2D::new(0.0, -1.0), speed, dt);
+        assert!(up.equals(start + Vec2D::new(0.0, -step), Some(1e-9)));
+
+        let diagonal = integrate_movement(start, Vec2D::new(1.0, 1.0), speed, dt);
+        assert!((diagonal.distance_to(start) - step).abs() < 1e-9);
+        assert!(diagonal.x > start.x && diagonal.y > start.y);
+
+        assert_eq!(integrate_movement(start, Vec2D::zero(), speed, dt), start);
+    }
+
+    fn wall(min: Vec2D, max: Vec2D) -> Hitbox {
+        Hitbox::Rect(RectangleHitbox::from_line(min, max))
+    }
+
+    #[test]
+    pub fn slide_along_wall() {
+        // a wall along y = 10, with the circle just above it
+        let obstacles = [wall(Vec2D::new(-50.0, 10.0), Vec2D::new(50.0, 12.0))];
+        let start = Vec2D::new(0.0, 8.5);
+
+        // down to the wall, then along it
+        let moved = move_with_collision(start, 1.0, Vec2D::new(1.0, 1.0), &obstacles);
+        assert!(moved.equals(Vec2D::new(1.0, 9.0), Some(1e-9)));
+
+        // nothing in the way
+        let moved = move_with_collision(start, 1.0, Vec2D::new(1.0, -1.0), &obstacles);
+        assert!(moved.equals(Vec2D::new(1.0, 7.5), Some(1e-9)));
+
+        // straight into the wall, there's nothing left to slide with once touching it
+        let moved = move_with_collision(start, 1.0, Vec2D::new(0.0, 1.0), &obstacles);
+        assert!(moved.equals(Vec2D::new(0.0, 9.0), Some(1e-9)));
+    }
+
+    #[test]
+    pub fn stop_in_corner() {
+        // walls below and to the right of the circle
+        let obstacles = [
+            wall(Vec2D::new(-50.0, 10.0), Vec2D::new(10.0, 12.0)),
+            wall(Vec2D::new(10.0, -50.0), Vec2D::new(12.0, 12.0)),
+        ];
+        let start = Vec2D::new(8.5, 8.5);
+
+        // ends up touching both walls
+        let moved = move_with_collision(start, 1.0, Vec2D::new(1.0, 1.0), &obstacles);
+        assert!(moved.equals(Vec2D::new(9.0, 9.0), Some(1e-9)));
+    }
+
+    #[test]
+    pub fn swept_move_thin_wall() {
+        // a wall half a unit thick, far thinner than the movement
+        let obstacles = [wall(Vec2D::new(10.0, -50.0), Vec2D::new(10.5, 50.0))];
+        let start = Vec2D::new(0.0, 0.0);
+
+        let moved = swept_move(start, 1.0, Vec2D::new(100.0, 0.0), &obstacles);
+        assert!(moved.equals(Vec2D::new(9.0, 0.0), Some(1e-9)));
+
+        // moving away from the wall isn't affected
+        let moved = swept_move(start, 1.0, Vec2D::new(-100.0, 0.0), &obstacles);
+        assert!(moved.equals(Vec2D::new(-100.0, 0.0), Some(1e-9)));
+
+        // passing around the end of the wall
+        let moved = swept_move(Vec2D::new(0.0, 52.0), 1.0, Vec2D::new(100.0, 0.0), &obstacles);
+        assert!(moved.equals(Vec2D::new(100.0, 52.0), Some(1e-9)));
+    }
+}
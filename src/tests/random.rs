@@ -0,0 +1,93 @@
+#[cfg(test)]
+pub mod random_utils {
+    use crate::utils::random::{seeded_shuffle, weighted_random, weighted_sample, GameRng};
+    use rand::SeedableRng;
+
+    #[test]
+    pub fn weighted_random_skips_zero_weights() {
+        let items = [0, 1, 2, 3, 4];
+        let weights = [0.1, 0.0, 0.2, 0.0, 0.3];
+
+        for _ in 0..10_000 {
+            let item = *weighted_random(&items, &weights);
+            assert!(item != 1 && item != 3, "picked zero-weight item {}", item);
+        }
+    }
+
+    #[test]
+    pub fn weighted_random_single_item() {
+        let items = ["a", "b", "c"];
+        let weights = [0.0, 1e-300, 0.0];
+
+        for _ in 0..1000 {
+            assert_eq!("b", *weighted_random(&items, &weights));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn weighted_random_no_weights() {
+        weighted_random(&[1, 2], &[0.0, 0.0]);
+    }
+
+    #[test]
+    pub fn seeded_shuffle_is_reproducible() {
+        let items: Vec<u32> = (0..50).collect();
+        let shuffled = |seed: u64| {
+            let mut copy = items.clone();
+            seeded_shuffle(&mut copy, seed);
+            copy
+        };
+
+        assert_eq!(shuffled(1234), shuffled(1234));
+        assert_ne!(shuffled(1234), shuffled(1235));
+        assert_ne!(items, shuffled(1234));
+
+        // still a permutation of the original items
+        let mut sorted = shuffled(1234);
+        sorted.sort();
+        assert_eq!(items, sorted);
+    }
+
+    #[test]
+    pub fn weighted_sample_distinct() {
+        let mut rng = GameRng::seed_from_u64(42);
+        let weights = [1.0, 5.0, 0.0, 2.0, 3.0];
+
+        for k in 0..=4 {
+            let mut drawn = weighted_sample(&weights, k, &mut rng);
+            assert_eq!(k, drawn.len());
+            assert!(!drawn.contains(&2));
+            drawn.sort();
+            drawn.dedup();
+            assert_eq!(k, drawn.len());
+        }
+
+        // more than there are items (or positive weights): everything that can be drawn
+        let mut all = weighted_sample(&weights, 10, &mut rng);
+        all.sort();
+        assert_eq!(vec![0, 1, 3, 4], all);
+        let mut all = weighted_sample(&[1.0, 1.0, 1.0], 5, &mut rng);
+        all.sort();
+        assert_eq!(vec![0, 1, 2], all);
+    }
+
+    #[test]
+    pub fn weighted_sample_favors_heavy_items() {
+        let mut rng = GameRng::seed_from_u64(7);
+        let weights = [1.0, 1.0, 8.0, 1.0, 1.0];
+        let mut counts = [0; 5];
+
+        for _ in 0..2000 {
+            for index in weighted_sample(&weights, 2, &mut rng) {
+                counts[index] += 1;
+            }
+        }
+
+        // the heavy item is in nearly every pair, the others share what's left
+        assert!(counts[2] > 1800, "{:?}", counts);
+        for (i, count) in counts.iter().enumerate().filter(|(i, _)| *i != 2) {
+            assert!(*count < counts[2] / 2, "item {} was drawn {} times", i, count);
+        }
+    }
+}
@@ -0,0 +1,41 @@
+#[cfg(test)]
+pub mod limiter {
+    use std::time::{Duration, Instant};
+    use crate::utils::rate_limit::TokenBucket;
+
+    #[test]
+    pub fn exhaust_and_refill() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(3.0, 0.5);
+
+        // a burst of three, then nothing
+        for _ in 0..3 {
+            assert!(bucket.try_take(start, 1.0));
+        }
+        assert!(!bucket.try_take(start, 1.0));
+
+        // half a token per second: one more after two seconds, but not after one
+        assert!(!bucket.try_take(start + Duration::from_secs(1), 1.0));
+        assert!(bucket.try_take(start + Duration::from_secs(2), 1.0));
+        assert!(!bucket.try_take(start + Duration::from_secs(2), 1.0));
+
+        // a long pause doesn't refill past the capacity
+        let later = start + Duration::from_secs(60);
+        assert!(!bucket.try_take(later, 4.0));
+        assert!(bucket.try_take(later, 3.0));
+        assert_eq!(0.0, bucket.tokens());
+    }
+
+    #[test]
+    pub fn refused_actions_cost_nothing() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(2.0, 1.0);
+
+        assert!(!bucket.try_take(start, 2.5));
+        assert!(bucket.try_take(start, 2.0));
+
+        // time going backwards neither refills nor drains the bucket
+        assert!(!bucket.try_take(start - Duration::from_millis(500), 0.5));
+        assert!(bucket.try_take(start + Duration::from_millis(500), 0.5));
+    }
+}
@@ -0,0 +1,43 @@
+#[cfg(test)]
+pub mod filters {
+    use crate::utils::smoothing::{Ema, Vec2DEma};
+    use crate::utils::vectors::Vec2D;
+
+    #[test]
+    pub fn ema_step_input() {
+        let mut ema = Ema::new(0.25);
+        assert_eq!(None, ema.value());
+        assert_eq!(0.0, ema.update(0.0));
+
+        // a step from 0 to 10: the output only ever rises, and closes in on 10
+        let mut previous = 0.0;
+        for _ in 0..50 {
+            let value = ema.update(10.0);
+            assert!(value > previous && value < 10.0);
+            previous = value;
+        }
+        assert!((10.0 - previous) < 1e-4);
+
+        ema.reset();
+        assert_eq!(-3.0, ema.update(-3.0));
+    }
+
+    #[test]
+    pub fn vector_ema_step_input() {
+        let mut ema = Vec2DEma::new(0.5);
+        ema.update(Vec2D::zero());
+
+        assert_eq!(Vec2D::new(2.0, -1.0), ema.update(Vec2D::new(4.0, -2.0)));
+        assert_eq!(Vec2D::new(3.0, -1.5), ema.update(Vec2D::new(4.0, -2.0)));
+        for _ in 0..40 {
+            ema.update(Vec2D::new(4.0, -2.0));
+        }
+        assert!(ema.value().unwrap().equals(Vec2D::new(4.0, -2.0), Some(1e-9)));
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn ema_invalid_alpha() {
+        Ema::new(0.0);
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(test)]
+pub mod custom_team {
+    use std::borrow::Cow;
+    use crate::typings::{CustomTeamPlayerInfo, OwnedCustomTeamPlayerInfo};
+
+    #[test]
+    pub fn owned_player_info_builder() {
+        let info = OwnedCustomTeamPlayerInfo::new(4, "limenade", "hazel_jumpsuit");
+        assert_eq!((None, None, None), (info.is_leader, info.badge.clone(), info.name_color));
+
+        let info = info.with_leader(true).with_badge("bdg_suroi").with_name_color(0x00ff00);
+        assert_eq!(Some(true), info.is_leader);
+        assert_eq!(Some(String::from("bdg_suroi")), info.badge);
+        assert_eq!(Some(0x00ff00), info.name_color);
+    }
+
+    #[test]
+    pub fn player_info_round_trip() {
+        let name = String::from("limenade");
+        let borrowed = CustomTeamPlayerInfo {
+            id: 4,
+            is_leader: Some(false),
+            name: Cow::Borrowed(&name),
+            skin: Cow::Borrowed("hazel_jumpsuit"),
+            badge: Some(Cow::Borrowed("bdg_suroi")),
+            name_color: None,
+        };
+
+        let owned = borrowed.to_owned_info();
+        assert_eq!(owned, OwnedCustomTeamPlayerInfo::from(&borrowed));
+        assert_eq!(borrowed, owned.as_borrowed());
+        assert_eq!(borrowed, CustomTeamPlayerInfo::from(owned));
+    }
+}
+
+#[cfg(test)]
+pub mod orientations {
+    use crate::typings::Orientation;
+
+    #[test]
+    pub fn add_wraps_around() {
+        let all = [Orientation::Up, Orientation::Right, Orientation::Down, Orientation::Left];
+
+        for (i, &a) in all.iter().enumerate() {
+            for (j, &b) in all.iter().enumerate() {
+                assert_eq!(a + b, all[(i + j) % 4], "{a:?} + {b:?}");
+                assert_eq!(a + b, b + a);
+            }
+        }
+    }
+}
@@ -1,5 +1,9 @@
 #[cfg(test)]
 pub mod vector {
+    use std::collections::HashSet;
+    use crate::utils::math::consts::{HALF_PI, PI};
+    use crate::utils::math::geometry;
+    use crate::utils::hitbox::{Collidable, RectangleHitbox};
     use crate::utils::vectors::Vec2D;
 
     #[test]
@@ -33,6 +37,8 @@ pub mod vector {
         let vec2: Vec2D = Vec2D::new(10.0, 10.0);
 
         assert_eq!(5.0*10.0*2.0, vec1 * vec2);
+        assert_eq!(5.0*10.0*2.0, vec1.dot(vec2));
+        assert_eq!(-2.0, Vec2D::new(1.0, -2.0).dot(Vec2D::new(4.0, 3.0)));
     }
 
     #[test]
@@ -64,4 +70,301 @@ pub mod vector {
         assert_eq!(Vec2D::new(-5.0,-5.0), -vec1);
     }
 
+    #[test]
+    pub fn assign_ops() {
+        let mut vec1: Vec2D = Vec2D::new(5.0, 5.0);
+
+        vec1 += Vec2D::new(1.0, 2.0);
+        assert_eq!(Vec2D::new(6.0, 7.0), vec1);
+
+        vec1 -= Vec2D::new(4.0, 4.0);
+        assert_eq!(Vec2D::new(2.0, 3.0), vec1);
+
+        vec1 *= 3.0;
+        assert_eq!(Vec2D::new(6.0, 9.0), vec1);
+    }
+
+    #[test]
+    pub fn divide() {
+        let mut vec1: Vec2D = Vec2D::new(10.0, 20.0);
+        assert_eq!(Vec2D::new(5.0, 10.0), vec1 / 2.0);
+
+        vec1 /= 2.0;
+        assert_eq!(Vec2D::new(5.0, 10.0), vec1);
+
+        let vec2 = Vec2D::new(1.0, 0.0) / 0.0;
+        assert!(vec2.x.is_infinite() && vec2.y.is_nan());
+    }
+
+    #[test]
+    pub fn component_mul() {
+        let vec1: Vec2D = Vec2D::new(2.0, -3.0);
+        let vec2: Vec2D = Vec2D::new(4.0, 5.0);
+
+        assert_eq!(Vec2D::new(8.0, -15.0), vec1.component_mul(vec2));
+    }
+
+    #[test]
+    pub fn distance() {
+        let vec1: Vec2D = Vec2D::new(1.0, 2.0);
+        let vec2: Vec2D = Vec2D::new(4.0, 6.0);
+
+        assert_eq!(5.0, vec1.distance_to(vec2));
+        assert_eq!(25.0, vec1.distance_squared_to(vec2));
+        assert_eq!(geometry::distance(vec1, vec2), vec1.distance_to(vec2));
+        assert_eq!(geometry::distance_squared(vec1, vec2), vec1.distance_squared_to(vec2));
+    }
+
+    #[test]
+    pub fn transform() {
+        let vec1: Vec2D = Vec2D::new(3.0, -1.0);
+        let pivot: Vec2D = Vec2D::new(1.0, 2.0);
+        let translate: Vec2D = Vec2D::new(-4.0, 0.5);
+        let angle = 0.7;
+        let scale = 1.5;
+
+        let sequential = pivot + (vec1 - pivot).rotate(angle) * scale + translate;
+        assert!(sequential.equals(vec1.transform(pivot, scale, angle, translate), Some(1e-9)));
+    }
+
+    #[test]
+    pub fn clamp_length() {
+        let vec1: Vec2D = Vec2D::new(3.0, 4.0);
+
+        assert!(Vec2D::new(1.8, 2.4).equals(vec1.clamp_length(3.0), Some(1e-9)));
+        assert_eq!(vec1, vec1.clamp_length(10.0));
+        assert_eq!(Vec2D::new(0.0, 0.0), Vec2D::new(0.0, 0.0).clamp_length(0.0));
+    }
+
+    #[test]
+    pub fn with_length() {
+        let vec1: Vec2D = Vec2D::new(3.0, 4.0);
+
+        assert!(Vec2D::new(6.0, 8.0).equals(vec1.with_length(10.0), Some(1e-9)));
+        assert_eq!(Vec2D::new(0.0, 0.0), Vec2D::new(0.0, 0.0).with_length(5.0));
+    }
+
+    #[test]
+    pub fn min_max_components() {
+        let vec1: Vec2D = Vec2D::new(-3.0, 2.0);
+        let vec2: Vec2D = Vec2D::new(1.0, -4.0);
+
+        assert_eq!(Vec2D::new(-3.0, -4.0), vec1.min_components(vec2));
+        assert_eq!(Vec2D::new(1.0, 2.0), vec1.max_components(vec2));
+        assert_eq!(-3.0, vec1.min_component());
+        assert_eq!(2.0, vec1.max_component());
+        assert_eq!(-4.0, vec2.min_component());
+        assert_eq!(1.0, vec2.max_component());
+    }
+
+    #[test]
+    pub fn rounding() {
+        let vec1: Vec2D = Vec2D::new(1.5, -0.5);
+        let vec2: Vec2D = Vec2D::new(-2.3, 2.7);
+
+        assert_eq!(Vec2D::new(1.0, -1.0), vec1.floor());
+        assert_eq!(Vec2D::new(2.0, -0.0), vec1.ceil());
+        assert_eq!(Vec2D::new(2.0, -1.0), vec1.round());
+
+        assert_eq!(Vec2D::new(-3.0, 2.0), vec2.floor());
+        assert_eq!(Vec2D::new(-2.0, 3.0), vec2.ceil());
+        assert_eq!(Vec2D::new(-2.0, 3.0), vec2.round());
+    }
+
+    #[test]
+    pub fn quantized_key() {
+        let vec1: Vec2D = Vec2D::new(10.001, -4.998);
+        let vec2: Vec2D = Vec2D::new(9.999, -5.002);
+        let vec3: Vec2D = Vec2D::new(10.02, -5.0);
+
+        assert_eq!(vec1.quantized_key(0.01), vec2.quantized_key(0.01));
+        assert_ne!(vec1.quantized_key(0.01), vec3.quantized_key(0.01));
+        assert_eq!((1000, -500), vec1.quantized_key(0.01));
+    }
+
+    #[test]
+    pub fn snap_to_grid() {
+        assert_eq!(Vec2D::new(32.0, 64.0), Vec2D::new(40.0, 50.0).snap_to_grid(32.0));
+        assert_eq!(Vec2D::new(32.0, 0.0), Vec2D::new(32.0, 0.0).snap_to_grid(32.0));
+        assert_eq!(Vec2D::new(-32.0, -64.0), Vec2D::new(-40.0, -50.0).snap_to_grid(32.0));
+    }
+
+    #[test]
+    pub fn grid_cell() {
+        assert_eq!((1, 1), Vec2D::new(40.0, 50.0).grid_cell(32.0));
+        assert_eq!((1, 0), Vec2D::new(32.0, 0.0).grid_cell(32.0));
+        assert_eq!((0, 0), Vec2D::new(31.9, 31.9).grid_cell(32.0));
+        assert_eq!((-1, -2), Vec2D::new(-0.1, -33.0).grid_cell(32.0));
+        assert_eq!((-1, -1), Vec2D::new(-32.0, -32.0).grid_cell(32.0));
+    }
+
+    #[test]
+    pub fn packed_cell() {
+        assert_eq!(0, Vec2D::new(0.0, 0.0).to_packed_cell(32.0, 10));
+        assert_eq!(21, Vec2D::new(40.0, 70.0).to_packed_cell(32.0, 10));
+        assert!(Vec2D::new(48.0, 80.0).equals(Vec2D::from_packed_cell(21, 32.0, 10), None));
+
+        for pos in [Vec2D::new(5.5, 0.0), Vec2D::new(100.0, 200.0), Vec2D::new(319.9, 12.3), Vec2D::new(0.0, 1e6)] {
+            let index = pos.to_packed_cell(32.0, 10);
+            assert_eq!(index, Vec2D::from_packed_cell(index, 32.0, 10).to_packed_cell(32.0, 10));
+            assert_eq!(pos.grid_cell(32.0), Vec2D::from_packed_cell(index, 32.0, 10).grid_cell(32.0));
+        }
+
+        // the last column wraps around to the next row
+        assert_eq!(9, Vec2D::new(319.9, 0.0).to_packed_cell(32.0, 10));
+        assert_eq!(10, Vec2D::new(0.0, 32.0).to_packed_cell(32.0, 10));
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of a grid 10 cells wide")]
+    pub fn packed_cell_out_of_grid() {
+        Vec2D::new(320.0, 0.0).to_packed_cell(32.0, 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of a grid 10 cells wide")]
+    pub fn packed_cell_negative() {
+        Vec2D::new(5.0, -0.5).to_packed_cell(32.0, 10);
+    }
+
+    #[test]
+    pub fn bits_key() {
+        let mut set: HashSet<(u64, u64)> = HashSet::new();
+
+        set.insert(Vec2D::new(1.5, 0.0).to_bits_key());
+        set.insert(Vec2D::new(1.5, -0.0).to_bits_key());
+        assert_eq!(1, set.len());
+
+        set.insert(Vec2D::new(1.5, 0.1).to_bits_key());
+        assert_eq!(2, set.len());
+    }
+
+    #[test]
+    pub fn conversions() {
+        let vec1: Vec2D = (1.5, -2.0).into();
+        let vec2: Vec2D = [1.5, -2.0].into();
+        assert_eq!(Vec2D::new(1.5, -2.0), vec1);
+        assert_eq!(vec1, vec2);
+
+        let tuple: (f64, f64) = vec1.into();
+        let array: [f64; 2] = vec1.into();
+        assert_eq!((1.5, -2.0), tuple);
+        assert_eq!([1.5, -2.0], array);
+        assert_eq!(array, vec1.to_array());
+
+        assert_eq!(vec1, Vec2D::from(<(f64, f64)>::from(vec1)));
+        assert_eq!(vec1, Vec2D::from(<[f64; 2]>::from(vec1)));
+    }
+
+    #[test]
+    pub fn perpendicular() {
+        let vec1: Vec2D = Vec2D::new(3.0, -1.5);
+
+        assert!(vec1.dot(vec1.perpendicular()).abs() < 1e-12);
+        assert!(vec1.dot(vec1.perpendicular_cw()).abs() < 1e-12);
+        assert_eq!(Vec2D::new(1.5, 3.0), vec1.perpendicular_ccw());
+        assert_eq!(Vec2D::new(-1.5, -3.0), vec1.perpendicular_cw());
+        assert_eq!(vec1.perpendicular_ccw(), vec1.perpendicular());
+    }
+
+    #[test]
+    pub fn angle() {
+        let vec1: Vec2D = Vec2D::new(3.0, 0.0);
+
+        assert!((vec1.angle(Vec2D::new(0.0, 5.0)) - HALF_PI).abs() < 1e-12);
+        assert_eq!(0.0, vec1.angle(Vec2D::new(0.1, 0.0)));
+        assert_eq!(PI, vec1.angle(Vec2D::new(-2.0, 0.0)));
+
+        let vec2: Vec2D = Vec2D::new(0.1, 0.7);
+        assert_eq!(0.0, vec2.angle(vec2 * 3.0));
+    }
+
+    #[test]
+    pub fn reflect() {
+        let vec1: Vec2D = Vec2D::new(3.0, -2.0);
+        let wall_normal: Vec2D = Vec2D::new(-1.0, 0.0);
+
+        assert_eq!(Vec2D::new(-3.0, -2.0), vec1.reflect(wall_normal));
+        assert_eq!(Vec2D::new(-3.0, -2.0), vec1.reflect_unnormalized(wall_normal * 5.0));
+    }
+
+    #[test]
+    pub fn sanitize() {
+        let fallback = Vec2D::new(1.0, 2.0);
+        let fine = Vec2D::new(3.0, -4.0);
+        assert!(fine.is_finite());
+        assert_eq!(fine.sanitize(fallback), fine);
+
+        for bad in [
+            Vec2D::new(f64::NAN, 0.0),
+            Vec2D::new(0.0, f64::NAN),
+            Vec2D::new(f64::INFINITY, 0.0),
+            Vec2D::new(0.0, f64::NEG_INFINITY),
+        ] {
+            assert!(!bad.is_finite());
+            assert_eq!(bad.sanitize(fallback), fallback);
+        }
+    }
+
+    #[test]
+    pub fn move_towards() {
+        let start = Vec2D::new(0.0, 0.0);
+        let target = Vec2D::new(3.0, 4.0);
+
+        assert!(start.move_towards(target, 1.0).equals(Vec2D::new(0.6, 0.8), None));
+        assert!(start.move_towards(target, 2.5).equals(Vec2D::new(1.5, 2.0), None));
+
+        assert_eq!(start.move_towards(target, 5.0), target);
+        assert_eq!(start.move_towards(target, 10.0), target);
+        assert_eq!(target.move_towards(target, 0.0), target);
+    }
+
+    #[test]
+    pub fn constants() {
+        assert_eq!((Vec2D::zero().x, Vec2D::zero().y), (0.0, 0.0));
+        assert_eq!((Vec2D::one().x, Vec2D::one().y), (1.0, 1.0));
+        assert_eq!((Vec2D::unit_x().x, Vec2D::unit_x().y), (1.0, 0.0));
+        assert_eq!((Vec2D::unit_y().x, Vec2D::unit_y().y), (0.0, 1.0));
+    }
+
+    #[test]
+    pub fn rotate_around() {
+        let pivot = Vec2D::new(2.0, 3.0);
+
+        assert!(Vec2D::new(4.0, 3.0)
+            .rotate_around(pivot, HALF_PI)
+            .equals(Vec2D::new(2.0, 5.0), None));
+        assert!(Vec2D::new(4.0, 3.0)
+            .rotate_around(pivot, -HALF_PI)
+            .equals(Vec2D::new(2.0, 1.0), None));
+        assert_eq!(pivot.rotate_around(pivot, 1.0), pivot);
+    }
+
+    #[test]
+    pub fn bounce() {
+        let wall = RectangleHitbox::from_line(Vec2D::new(10.0, -5.0), Vec2D::new(12.0, 5.0)).as_hitbox();
+
+        // hits the left face at a 45° angle and comes back the same way on the x axis
+        let (contact, velocity) = Vec2D::new(6.0, 0.0).bounce(Vec2D::new(8.0, 8.0), &wall).unwrap();
+        assert!(contact.equals(Vec2D::new(10.0, 4.0), None));
+        assert!(velocity.equals(Vec2D::new(-8.0, 8.0), None));
+
+        // head-on
+        let (contact, velocity) = Vec2D::new(0.0, 1.0).bounce(Vec2D::new(20.0, 0.0), &wall).unwrap();
+        assert!(contact.equals(Vec2D::new(10.0, 1.0), None));
+        assert!(velocity.equals(Vec2D::new(-20.0, 0.0), None));
+
+        // stops short of the wall
+        assert!(Vec2D::new(0.0, 0.0).bounce(Vec2D::new(5.0, 0.0), &wall).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    pub fn serde_round_trip() {
+        let vec = Vec2D::new(-1.5, 1024.25);
+
+        let json = serde_json::to_string(&vec).unwrap();
+        assert_eq!(json, r#"{"x":-1.5,"y":1024.25}"#);
+        assert_eq!(serde_json::from_str::<Vec2D>(&json).unwrap(), vec);
+    }
 }
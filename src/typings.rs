@@ -1,10 +1,12 @@
 use super::utils::math::consts::*;
+use super::utils::bitstream::{Deserialize, Serialize, Stream};
 use super::utils::vectors::Vec2D;
 use crate::constants::TeamSize;
+use std::borrow::Cow;
 use std::ops::Add;
 use phf::phf_map;
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Orientation {
     Up,
     Right,
@@ -16,13 +18,11 @@ impl Add for Orientation {
     type Output = Orientation;
 
     fn add(self, rhs: Self) -> Self::Output {
-        let added = self as u8 + rhs as u8 % 4;
-        match added {
+        match (self as u8 + rhs as u8) % 4 {
             0 => Orientation::Up,
             1 => Orientation::Right,
             2 => Orientation::Down,
-            3 => Orientation::Left,
-            _ => Orientation::Up,
+            _ => Orientation::Left,
         }
     }
 }
@@ -38,6 +38,23 @@ impl Orientation {
     }
 }
 
+impl Serialize for Orientation {
+    fn write_to(&self, stream: &mut impl Stream) {
+        stream.write_bits_us(*self as u8, 2);
+    }
+}
+
+impl Deserialize for Orientation {
+    fn read_from(stream: &mut impl Stream) -> Self {
+        match stream.read_bits(2) {
+            0 => Orientation::Up,
+            1 => Orientation::Right,
+            2 => Orientation::Down,
+            _ => Orientation::Left,
+        }
+    }
+}
+
 #[derive(Copy, Clone)]
 pub enum Variant {
     A,
@@ -50,12 +67,14 @@ pub enum Variant {
     H,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum GameRejectType {
     Warn,
     Temp,
     Perma,
 }
 
+#[derive(Clone, Debug, PartialEq)]
 pub enum GameResponse {
     Success {
         game_id: u32,
@@ -67,15 +86,114 @@ pub enum GameResponse {
     },
 }
 
+/// The first packet a client sends, asking to spawn into the game
+#[derive(Clone, Debug, PartialEq)]
+pub struct JoinPacket {
+    pub name: String,
+    pub skin: String,
+    pub badge: Option<String>,
+}
+
+// Strings are `Cow`s so that player info can either borrow existing data
+// or own what was decoded from a stream
+#[derive(Clone, Debug, PartialEq)]
 pub struct CustomTeamPlayerInfo<'a> {
-    id: u32,
-    is_leader: Option<bool>,
-    name: &'a str,
-    skin: &'a str,
-    badge: Option<&'a str>,
-    name_color: Option<i32>,
+    pub id: u32,
+    pub is_leader: Option<bool>,
+    pub name: Cow<'a, str>,
+    pub skin: Cow<'a, str>,
+    pub badge: Option<Cow<'a, str>>,
+    pub name_color: Option<i32>,
+}
+
+impl CustomTeamPlayerInfo<'_> {
+    pub fn to_owned_info(&self) -> OwnedCustomTeamPlayerInfo {
+        OwnedCustomTeamPlayerInfo::from(self)
+    }
+}
+
+/// `CustomTeamPlayerInfo` without any borrows, to keep around in collections.
+/// Optional fields can be set while building it with the `with_*` methods:
+/// ```ignore
+/// OwnedCustomTeamPlayerInfo::new(id, "name", "skin").with_leader(true).with_badge("badge")
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedCustomTeamPlayerInfo {
+    pub id: u32,
+    pub is_leader: Option<bool>,
+    pub name: String,
+    pub skin: String,
+    pub badge: Option<String>,
+    pub name_color: Option<i32>,
+}
+
+impl OwnedCustomTeamPlayerInfo {
+    pub fn new(id: u32, name: &str, skin: &str) -> Self {
+        OwnedCustomTeamPlayerInfo {
+            id,
+            is_leader: None,
+            name: name.to_string(),
+            skin: skin.to_string(),
+            badge: None,
+            name_color: None,
+        }
+    }
+
+    pub fn with_leader(mut self, is_leader: bool) -> Self {
+        self.is_leader = Some(is_leader);
+        self
+    }
+
+    pub fn with_badge(mut self, badge: &str) -> Self {
+        self.badge = Some(badge.to_string());
+        self
+    }
+
+    pub fn with_name_color(mut self, name_color: i32) -> Self {
+        self.name_color = Some(name_color);
+        self
+    }
+
+    /// Borrows this player info's strings
+    pub fn as_borrowed(&self) -> CustomTeamPlayerInfo<'_> {
+        CustomTeamPlayerInfo {
+            id: self.id,
+            is_leader: self.is_leader,
+            name: Cow::Borrowed(&self.name),
+            skin: Cow::Borrowed(&self.skin),
+            badge: self.badge.as_deref().map(Cow::Borrowed),
+            name_color: self.name_color,
+        }
+    }
+}
+
+impl From<&CustomTeamPlayerInfo<'_>> for OwnedCustomTeamPlayerInfo {
+    fn from(info: &CustomTeamPlayerInfo) -> Self {
+        OwnedCustomTeamPlayerInfo {
+            id: info.id,
+            is_leader: info.is_leader,
+            name: info.name.to_string(),
+            skin: info.skin.to_string(),
+            badge: info.badge.as_ref().map(|badge| badge.to_string()),
+            name_color: info.name_color,
+        }
+    }
+}
+
+impl From<OwnedCustomTeamPlayerInfo> for CustomTeamPlayerInfo<'static> {
+    fn from(info: OwnedCustomTeamPlayerInfo) -> Self {
+        CustomTeamPlayerInfo {
+            id: info.id,
+            is_leader: info.is_leader,
+            name: Cow::Owned(info.name),
+            skin: Cow::Owned(info.skin),
+            badge: info.badge.map(Cow::Owned),
+            name_color: info.name_color,
+        }
+    }
 }
 
+#[derive(Clone, Debug, PartialEq)]
 pub enum CustomTeamMessage<'a> {
     Join {
         id: u32,
@@ -83,7 +201,7 @@ pub enum CustomTeamMessage<'a> {
         is_leader: bool,
         auto_fill: bool,
         locked: bool,
-        players: &'a [CustomTeamPlayerInfo<'a>],
+        players: Vec<CustomTeamPlayerInfo<'a>>,
     },
     PlayerJoin(CustomTeamPlayerInfo<'a>),
     PlayerLeave {
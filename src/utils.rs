@@ -9,3 +9,8 @@ pub mod decimal;
 pub mod string_utils;
 pub mod misc;
 pub mod ansi_coloring;
+pub mod navigation;
+pub mod physics;
+pub mod smoothing;
+pub mod rate_limit;
+pub mod arena;
@@ -0,0 +1,106 @@
+/// Handle to a value stored in an [`Arena`]. The generation tells apart values that
+/// were stored in the same slot at different times, so a handle kept after its value
+/// was removed won't resolve to whatever was inserted there later
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Index {
+    slot: u32,
+    generation: u32,
+}
+
+#[derive(Debug)]
+enum Entry<T> {
+    Occupied { generation: u32, value: T },
+    /// A free slot, remembering the generation of its last value and the next free slot
+    Free { generation: u32, next_free: Option<u32> },
+}
+
+/// Slab of values addressed by generational indices. Removed slots are reused by later
+/// insertions instead of growing the storage, which keeps entity churn allocation-free
+#[derive(Debug)]
+pub struct Arena<T> {
+    entries: Vec<Entry<T>>,
+    free_head: Option<u32>,
+    len: usize,
+}
+
+impl<T> Arena<T> {
+    pub fn new() -> Arena<T> {
+        Arena {
+            entries: Vec::new(),
+            free_head: None,
+            len: 0,
+        }
+    }
+
+    /// Stores `value`, reusing a free slot if there is one
+    pub fn insert(&mut self, value: T) -> Index {
+        self.len += 1;
+
+        match self.free_head {
+            Some(slot) => {
+                let entry = &mut self.entries[slot as usize];
+                let Entry::Free { generation, next_free } = *entry else {
+                    unreachable!("Free list points at an occupied slot")
+                };
+                let generation = generation.wrapping_add(1);
+
+                self.free_head = next_free;
+                *entry = Entry::Occupied { generation, value };
+                Index { slot, generation }
+            }
+            None => {
+                let slot = u32::try_from(self.entries.len()).expect("Arena is full");
+
+                self.entries.push(Entry::Occupied { generation: 0, value });
+                Index { slot, generation: 0 }
+            }
+        }
+    }
+
+    /// Removes and returns the value at `index`, or `None` if it is stale
+    pub fn remove(&mut self, index: Index) -> Option<T> {
+        let entry = self.entries.get_mut(index.slot as usize)?;
+        if !matches!(entry, Entry::Occupied { generation, .. } if *generation == index.generation) {
+            return None;
+        }
+
+        let free = Entry::Free { generation: index.generation, next_free: self.free_head };
+        let Entry::Occupied { value, .. } = std::mem::replace(entry, free) else { unreachable!() };
+
+        self.free_head = Some(index.slot);
+        self.len -= 1;
+        Some(value)
+    }
+
+    pub fn get(&self, index: Index) -> Option<&T> {
+        match self.entries.get(index.slot as usize)? {
+            Entry::Occupied { generation, value } if *generation == index.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, index: Index) -> Option<&mut T> {
+        match self.entries.get_mut(index.slot as usize)? {
+            Entry::Occupied { generation, value } if *generation == index.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn contains(&self, index: Index) -> bool {
+        self.get(index).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,4 +1,6 @@
 use std::cmp::min;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
 
 use super::{
     decimal::DecimalSerializer,
@@ -140,13 +142,11 @@ pub trait Stream {
     }
 
     fn write_uint64<T: Into<u64>>(&mut self, value: T) {
-        let into = Into::<u64>::into(value);
-        self.write_bits_us((into & 0xFFFFFFFF) as u32, 32);
-        self.write_bits_us((into >> 32) as u32, 32);
+        self.write_bits_wide(Into::<u64>::into(value) as u128, 64);
     }
 
     fn read_uint64(&mut self) -> u64 {
-        self.read_bits(32) as u64 + ((self.read_bits(32) as u64) << 32)
+        self.read_bits_wide(64) as u64
     }
 
     // int128
@@ -159,20 +159,31 @@ pub trait Stream {
     }
 
     fn write_uint128<T: Into<u128>>(&mut self, value: T) {
-        let into = Into::<u128>::into(value);
-        // needless operations added for padding & visual clarity
-        self.write_bits_us((into & 0xFFFFFFFF) as u32, 32);
-        self.write_bits_us(((into >> 0x20) & 0xFFFFFFFF) as u32, 32);
-        self.write_bits_us(((into >> 0x40) & 0xFFFFFFFF) as u32, 32);
-        self.write_bits_us(((into >> 0x60) & 0xFFFFFFFF) as u32, 32);
+        self.write_bits_wide(Into::<u128>::into(value), 128);
     }
 
     fn read_uint128(&mut self) -> u128 {
-        // needless operations added for padding & visual clarity
-        (self.read_bits(32) as u128)
-            + ((self.read_bits(32) as u128) << 0x20)
-            + ((self.read_bits(32) as u128) << 0x40)
-            + ((self.read_bits(32) as u128) << 0x60)
+        self.read_bits_wide(128)
+    }
+
+    // wide values
+    /// Writes *up to 128 bits*, split into chunks of at most 32 bits. Little-endian streams
+    /// write the least significant chunk first, big-endian streams the most significant one
+    fn write_bits_wide(&mut self, value: u128, bits: usize) {
+        assert!(bits <= 128, "Wide writes must be at most 128 bits");
+
+        for (shift, chunk) in wide_chunks(bits, self.get_endianness()) {
+            self.write_bits_us(((value >> shift) & chunk_mask(chunk)) as u32, chunk);
+        }
+    }
+
+    /// Reads *up to 128 bits* written by `write_bits_wide`
+    fn read_bits_wide(&mut self, bits: usize) -> u128 {
+        assert!(bits <= 128, "Wide reads must be at most 128 bits");
+
+        wide_chunks(bits, self.get_endianness())
+            .into_iter()
+            .fold(0, |value, (shift, chunk)| value | (self.read_bits(chunk) as u128) << shift)
     }
 
     // floats
@@ -286,6 +297,18 @@ pub trait Stream {
     }
 }
 
+/// Types that know how to write themselves to a [`Stream`]. Structs implement this by
+/// writing their fields in declaration order, mirroring their [`Deserialize`] impl
+pub trait Serialize {
+    fn write_to(&self, stream: &mut impl Stream);
+}
+
+/// Types that can be read back from a [`Stream`], consuming exactly the bits their
+/// [`Serialize`] impl wrote
+pub trait Deserialize: Sized {
+    fn read_from(stream: &mut impl Stream) -> Self;
+}
+
 impl Stream for BitStream {
     /// Returns the size of this bitstream in bytes
     #[inline(always)]
@@ -468,9 +491,57 @@ impl Stream for BitStream {
     }
 }
 
+/// Splits a value of `bits` bits into chunks of at most 32 bits, as `(shift, size)` pairs
+/// in the order they're written. Big-endian streams start with the (possibly shorter)
+/// most significant chunk
+fn wide_chunks(bits: usize, endianness: Endianness) -> Vec<(usize, usize)> {
+    let mut chunks: Vec<(usize, usize)> = (0..bits)
+        .step_by(32)
+        .map(|shift| (shift, min(32, bits - shift)))
+        .collect();
+
+    if endianness == Endianness::Big {
+        chunks.reverse();
+    }
+    chunks
+}
+
+fn chunk_mask(bits: usize) -> u128 {
+    (1 << bits) - 1
+}
+
 #[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Endianness {
     #[default]
     Little,
     Big,
 }
+
+/// Errors from reading malformed or incompatible data off a stream
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StreamError {
+    /// The peer speaks a different version of the protocol
+    ProtocolMismatch { expected: u16, received: u16 },
+    /// The stream ended before everything could be read
+    UnexpectedEnd { needed: usize, available: usize },
+    /// A field was read fine, but its value isn't acceptable
+    InvalidField { field: &'static str, reason: String },
+}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::ProtocolMismatch { expected, received } => write!(
+                f,
+                "Protocol version mismatch: expected {}, received {}",
+                expected, received
+            ),
+            StreamError::UnexpectedEnd { needed, available } => {
+                write!(f, "Unexpected end of stream: needed {} bits, {} left", needed, available)
+            }
+            StreamError::InvalidField { field, reason } => write!(f, "Invalid {}: {}", field, reason),
+        }
+    }
+}
+
+impl Error for StreamError {}
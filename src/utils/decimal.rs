@@ -311,4 +311,47 @@ impl DecimalSerializer {
 
         res
     }
-}
\ No newline at end of file
+}
+
+/// Serializer for numbers within a known range, spreading `2 ** bits` evenly spaced values
+/// from `min` to `max` (both included). Unlike `DecimalSerializer`, precision is the same
+/// across the whole range, which suits positions, rotations and other bounded values better.
+/// Values outside of the range are clamped to it
+#[derive(Copy, Clone, Debug)]
+pub struct FixedPoint {
+    min: f64,
+    max: f64,
+    /// How many bits an encoded value occupies, at most 32
+    bits: usize,
+}
+
+impl FixedPoint {
+    pub fn new(min: f64, max: f64, bits: usize) -> FixedPoint {
+        assert!(bits <= 32, "Fixed-point values cannot exceed 32 bits, got {}", bits);
+        assert!(min < max, "Invalid range [{}, {}]", min, max);
+
+        FixedPoint { min, max, bits }
+    }
+
+    pub fn bits(&self) -> usize {
+        self.bits
+    }
+
+    /// The distance between two consecutive representable values; encoding a value
+    /// is off by at most half of this
+    pub fn step(&self) -> f64 {
+        (self.max - self.min) / self.steps()
+    }
+
+    pub fn encode(&self, value: f64) -> u32 {
+        ((value.clamp(self.min, self.max) - self.min) / (self.max - self.min) * self.steps() + 0.5).trunc() as u32
+    }
+
+    pub fn decode(&self, value: u32) -> f64 {
+        self.min + (self.max - self.min) * (value as f64) / self.steps()
+    }
+
+    fn steps(&self) -> f64 {
+        ((1u64 << self.bits) - 1) as f64
+    }
+}
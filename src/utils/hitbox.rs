@@ -1,19 +1,287 @@
 use core::f64;
+use std::sync::OnceLock;
 
 use super::math::{
-    collisions, collisions::distances, geometry, intersections, CollisionRecord,
+    consts::{PI, TAU}, collisions, collisions::distances, geometry, intersections, CollisionRecord,
     IntersectionResponse,
 };
-use super::random::{random_point_in_circle, random_float, random_item};
+use super::random::{random_point_in_circle, random_float, weighted_random, GameRng};
 use super::vectors::Vec2D;
 use crate::typings::Orientation;
+use rand::seq::SliceRandom;
+use rand::Rng;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Map data uses an internally tagged representation, e.g. `{"type": "rect", "min": [0, 0], "max": [4, 4]}`
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "lowercase"))]
 pub enum Hitbox {
     Circle(CircleHitbox),
     Rect(RectangleHitbox),
     Group(GroupHitbox),
     Polygon(PolygonHitbox),
+    Line(LineHitbox),
+    Capsule(CapsuleHitbox),
+}
+
+/// Vectors in hitbox data are written as `[x, y]` pairs, which keeps map files short
+#[cfg(feature = "serde")]
+mod vec_pair {
+    use super::Vec2D;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(vec: &Vec2D, serializer: S) -> Result<S::Ok, S::Error> {
+        <[f64; 2]>::from(*vec).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec2D, D::Error> {
+        <[f64; 2]>::deserialize(deserializer).map(Vec2D::from)
+    }
+}
+
+/// Same as `vec_pair`, for lists of vectors
+#[cfg(feature = "serde")]
+mod vec_pairs {
+    use super::Vec2D;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(vecs: &[Vec2D], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(vecs.iter().map(|vec| <[f64; 2]>::from(*vec)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Vec2D>, D::Error> {
+        Ok(Vec::<[f64; 2]>::deserialize(deserializer)?.into_iter().map(Vec2D::from).collect())
+    }
+}
+
+/// Checks whether two hitboxes collide, regardless of their variants and of the order
+/// they're passed in. Pairs that the concrete `collides_with` implementations don't
+/// handle (like two polygons) are dispatched to the right function here instead.
+pub fn hitboxes_collide(a: &Hitbox, b: &Hitbox) -> bool {
+    match (a, b) {
+        (Hitbox::Circle(a), _) => a.collides_with(b),
+        (Hitbox::Rect(a), _) => a.collides_with(b),
+        (Hitbox::Polygon(a), _) => a.collides_with(b),
+        (Hitbox::Group(a), _) => a.collides_with(b),
+        (Hitbox::Line(a), _) => a.collides_with(b),
+        (Hitbox::Capsule(a), _) => a.collides_with(b),
+    }
+}
+
+/// Measures the distance between two hitboxes, regardless of their variants and of
+/// the order they're passed in. Unlike `Collidable::distance_to`, this never panics:
+/// pairs that can't be measured (like an empty group) give `None` instead.
+pub fn hitbox_distance(a: &Hitbox, b: &Hitbox) -> Option<CollisionRecord> {
+    match (a, b) {
+        (Hitbox::Group(group), other) | (other, Hitbox::Group(group)) => group.distance_to(other),
+        (Hitbox::Circle(a), Hitbox::Circle(b)) => {
+            Some(distances::circles(a.position, a.radius, b.position, b.radius))
+        }
+        (Hitbox::Rect(rect), Hitbox::Circle(circle)) | (Hitbox::Circle(circle), Hitbox::Rect(rect)) => {
+            Some(distances::circle_rect(rect.min, rect.max, circle.position, circle.radius))
+        }
+        (Hitbox::Rect(a), Hitbox::Rect(b)) => Some(distances::rects(a.min, a.max, b.min, b.max)),
+        (Hitbox::Polygon(polygon), Hitbox::Circle(circle)) | (Hitbox::Circle(circle), Hitbox::Polygon(polygon)) => {
+            Some(distances::circle_polygon(&polygon.points, circle.position, circle.radius))
+        }
+        (Hitbox::Polygon(polygon), Hitbox::Rect(rect)) | (Hitbox::Rect(rect), Hitbox::Polygon(polygon)) => {
+            Some(distances::polygons(&polygon.points, &rect.corners()))
+        }
+        (Hitbox::Polygon(a), Hitbox::Polygon(b)) => Some(distances::polygons(&a.points, &b.points)),
+        (Hitbox::Line(line), Hitbox::Circle(circle)) | (Hitbox::Circle(circle), Hitbox::Line(line)) => {
+            Some(distances::circle_line(line.start, line.end, circle.position, circle.radius))
+        }
+        (Hitbox::Line(_), _) | (_, Hitbox::Line(_)) => None,
+        (Hitbox::Capsule(capsule), other) | (other, Hitbox::Capsule(capsule)) => match other {
+            Hitbox::Circle(_) | Hitbox::Rect(_) => capsule.distance_to(other),
+            _ => None,
+        },
+    }
+}
+
+/// Finds where the segment from `a` to `b` first enters a hitbox, regardless of its variant
+pub fn hitbox_intersects_line(hitbox: &Hitbox, a: Vec2D, b: Vec2D) -> Option<IntersectionResponse> {
+    match hitbox {
+        Hitbox::Circle(hitbox) => hitbox.intersects_line(a, b),
+        Hitbox::Rect(hitbox) => hitbox.intersects_line(a, b),
+        Hitbox::Group(hitbox) => hitbox.intersects_line(a, b),
+        Hitbox::Polygon(hitbox) => hitbox.intersects_line(a, b),
+        Hitbox::Line(hitbox) => hitbox.intersects_line(a, b),
+        Hitbox::Capsule(hitbox) => hitbox.intersects_line(a, b),
+    }
+}
+
+/// Pushes `hitbox` out of `other`, regardless of their variants; `other` never moves. Only circles,
+/// rectangles and groups of them can be resolved: anything involving a polygon, a line or a capsule
+/// is left as it is
+pub fn resolve_hitbox_collision(hitbox: &mut Hitbox, other: &mut Hitbox) {
+    match (hitbox, other) {
+        (Hitbox::Group(group), other) => {
+            group.bounds.take();
+            for hitbox in group.hitboxes.iter_mut() {
+                if hitboxes_collide(hitbox, other) {
+                    resolve_hitbox_collision(hitbox, other);
+                }
+            }
+        }
+        (hitbox, Hitbox::Group(group)) => {
+            for other in group.hitboxes.iter_mut() {
+                if hitboxes_collide(hitbox, other) {
+                    resolve_hitbox_collision(hitbox, other);
+                }
+            }
+        }
+        (Hitbox::Circle(hitbox), other @ (Hitbox::Circle(_) | Hitbox::Rect(_))) => hitbox.resolve_collision(other),
+        (Hitbox::Rect(hitbox), other @ (Hitbox::Circle(_) | Hitbox::Rect(_))) => hitbox.resolve_collision(other),
+        _ => {}
+    }
+}
+
+/// Pushes two overlapping circles apart, splitting the penetration between them in inverse
+/// proportion to their masses: the heavier circle moves less. Both masses must be positive;
+/// `f64::INFINITY` makes a circle immovable
+pub fn resolve_collision_mutual(a: &mut CircleHitbox, b: &mut CircleHitbox, mass_a: f64, mass_b: f64) {
+    let Some(collision) = intersections::circles(a.position, a.radius, b.position, b.radius) else {
+        return;
+    };
+
+    let (share_a, share_b) = match (mass_a.is_infinite(), mass_b.is_infinite()) {
+        (true, true) => return,
+        (true, false) => (0.0, 1.0),
+        (false, true) => (1.0, 0.0),
+        (false, false) => (mass_b / (mass_a + mass_b), mass_a / (mass_a + mass_b)),
+    };
+
+    // the collision's direction points from a to b
+    let push = collision.dir * collision.pen;
+    a.position = (a.position - push * share_a).sanitize(a.position);
+    b.position = (b.position + push * share_b).sanitize(b.position);
+}
+
+/// Returns the area of a hitbox, regardless of its variant
+pub fn hitbox_area(hitbox: &Hitbox) -> f64 {
+    match hitbox {
+        Hitbox::Circle(hitbox) => hitbox.area(),
+        Hitbox::Rect(hitbox) => hitbox.area(),
+        Hitbox::Group(hitbox) => hitbox.area(),
+        Hitbox::Polygon(hitbox) => hitbox.area(),
+        Hitbox::Line(hitbox) => hitbox.area(),
+        Hitbox::Capsule(hitbox) => hitbox.area(),
+    }
+}
+
+/// Checks whether a point lies inside a hitbox, regardless of its variant
+pub fn hitbox_contains(hitbox: &Hitbox, point: Vec2D) -> bool {
+    match hitbox {
+        Hitbox::Circle(hitbox) => hitbox.is_vec_inside(point),
+        Hitbox::Rect(hitbox) => hitbox.is_vec_inside(point),
+        Hitbox::Group(hitbox) => hitbox.is_vec_inside(point),
+        Hitbox::Polygon(hitbox) => hitbox.is_vec_inside(point),
+        Hitbox::Line(hitbox) => hitbox.is_vec_inside(point),
+        Hitbox::Capsule(hitbox) => hitbox.is_vec_inside(point),
+    }
+}
+
+/// Returns the outline of a hitbox as a list of polygons, one for each hitbox in a group.
+/// Circles and the ends of capsules are approximated by inscribed polygons with
+/// `circle_segments` sides, and lines
+/// give a degenerate polygon made of their two endpoints
+pub fn hitbox_outlines(hitbox: &Hitbox, circle_segments: usize) -> Vec<Vec<Vec2D>> {
+    match hitbox {
+        Hitbox::Circle(circle) => vec![circle.to_polygon(circle_segments).points],
+        Hitbox::Rect(rect) => vec![rect.corners().to_vec()],
+        Hitbox::Polygon(polygon) => vec![polygon.points.clone()],
+        Hitbox::Line(line) => vec![vec![line.start, line.end]],
+        Hitbox::Capsule(capsule) => vec![capsule.to_polygon(circle_segments).points],
+        Hitbox::Group(group) => group
+            .hitboxes
+            .iter()
+            .flat_map(|child| hitbox_outlines(child, circle_segments))
+            .collect(),
+    }
+}
+
+/// Returns the direction pointing out of a hitbox towards a circle overlapping it, or `None`
+/// if they don't overlap. For rectangles and polygons, this is the normal of the closest edge
+pub fn hitbox_contact_normal(hitbox: &Hitbox, center: Vec2D, radius: f64) -> Option<Vec2D> {
+    let outline_normal = |points: &[Vec2D]| {
+        let len = points.len();
+        let closest = (0..len)
+            .map(|i| geometry::closest_point_on_segment(center, points[i], points[(i + 1) % len]))
+            .min_by(|a, b| a.distance_squared_to(center).total_cmp(&b.distance_squared_to(center)))?;
+
+        let inside = hitbox_contains(hitbox, center);
+        if !inside && closest.distance_squared_to(center) >= radius * radius {
+            return None;
+        }
+
+        let normal = (center - closest).normalize(None);
+        Some(if inside { -normal } else { normal })
+    };
+
+    match hitbox {
+        Hitbox::Circle(circle) => {
+            intersections::circles(center, radius, circle.position, circle.radius).map(|collision| -collision.dir)
+        }
+        Hitbox::Rect(rect) => outline_normal(&rect.corners()),
+        Hitbox::Polygon(polygon) => outline_normal(&polygon.points),
+        Hitbox::Line(line) => outline_normal(&[line.start, line.end]),
+        Hitbox::Capsule(capsule) => {
+            let closest = geometry::closest_point_on_segment(center, capsule.start, capsule.end);
+            let reach = radius + capsule.radius;
+            (closest.distance_squared_to(center) < reach * reach).then(|| (center - closest).normalize(None))
+        }
+        Hitbox::Group(group) => group
+            .hitboxes
+            .iter()
+            .find_map(|child| hitbox_contact_normal(child, center, radius)),
+    }
+}
+
+/// Finds when a circle moving along `delta` first touches a hitbox, as a fraction of `delta`
+/// from `0` to `1`. Polygons, lines and capsules are approximated by their bounding box, and hitboxes the circle
+/// already overlaps are ignored so that it can move out of them
+pub fn hitbox_sweep(hitbox: &Hitbox, start: Vec2D, radius: f64, delta: Vec2D) -> Option<f64> {
+    match hitbox {
+        Hitbox::Circle(circle) => intersections::swept_circles(start, delta, radius, circle.position, circle.radius),
+        Hitbox::Rect(rect) => intersections::swept_circle_rect(start, delta, radius, rect.min, rect.max),
+        Hitbox::Polygon(polygon) => {
+            let bounds = polygon.as_rectangle();
+            intersections::swept_circle_rect(start, delta, radius, bounds.min, bounds.max)
+        }
+        Hitbox::Line(line) => {
+            let bounds = line.as_rectangle();
+            intersections::swept_circle_rect(start, delta, radius, bounds.min, bounds.max)
+        }
+        Hitbox::Capsule(capsule) => {
+            let bounds = capsule.as_rectangle();
+            intersections::swept_circle_rect(start, delta, radius, bounds.min, bounds.max)
+        }
+        Hitbox::Group(group) => group
+            .hitboxes
+            .iter()
+            .filter_map(|child| hitbox_sweep(child, start, radius, delta))
+            .min_by(f64::total_cmp),
+    }
+}
+
+/// Picks a point on the outline of a closed polygon, uniformly along its length
+fn random_outline_point(points: &[Vec2D], rng: &mut GameRng) -> Vec2D {
+    let len = points.len();
+    let edges = (0..len).map(|i| (points[i], points[(i + 1) % len]));
+    let perimeter: f64 = edges.clone().map(|(start, end)| start.distance_to(end)).sum();
+
+    let mut pick = rng.gen_range(0.0..perimeter);
+    for (start, end) in edges {
+        let length = start.distance_to(end);
+        if pick < length {
+            return start.lerp(end, pick / length);
+        }
+        pick -= length;
+    }
+
+    // floating-point error left some of the pick over, so it falls on the closing point
+    points[0]
 }
 
 pub trait Collidable {
@@ -25,17 +293,54 @@ pub trait Collidable {
     fn scale(&mut self, scale: f64);
     fn intersects_line(&self, a: Vec2D, b: Vec2D) -> Option<IntersectionResponse>;
     fn random_point(&self) -> Vec2D;
+    fn random_perimeter_point(&self, rng: &mut GameRng) -> Vec2D;
     fn as_rectangle(&self) -> RectangleHitbox;
+    /// A circle around the center (as given by `get_center`) enclosing the whole hitbox,
+    /// for round broad-phase checks
+    fn bounding_circle(&self) -> CircleHitbox;
     fn is_vec_inside(&self, vec: Vec2D) -> bool;
+    fn overlaps_rect(&self, min: Vec2D, max: Vec2D) -> bool;
     fn get_center(&self) -> Vec2D;
+    fn area(&self) -> f64;
     fn panic_unknown_subclass(other: &Hitbox);
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CircleHitbox {
+    #[cfg_attr(feature = "serde", serde(with = "vec_pair"))]
     position: Vec2D,
     radius: f64,
 }
+
+impl CircleHitbox {
+    pub fn new(position: Vec2D, radius: f64) -> CircleHitbox {
+        CircleHitbox {
+            position,
+            radius
+        }
+    }
+
+    pub fn position(&self) -> Vec2D {
+        self.position
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// Approximates this circle with a regular polygon inscribed in it
+    pub fn to_polygon(&self, segments: usize) -> PolygonHitbox {
+        assert!(segments >= 3, "A polygon needs at least 3 segments, got {}", segments);
+
+        let step = TAU / segments as f64;
+        PolygonHitbox::new(
+            (0..segments)
+                .map(|i| self.position + Vec2D::from_polar(step * i as f64, Some(self.radius)))
+                .collect()
+        )
+    }
+}
 impl Collidable for CircleHitbox {
     fn as_hitbox(&self) -> Hitbox {
         Hitbox::Circle(self.clone())
@@ -57,7 +362,9 @@ impl Collidable for CircleHitbox {
                 collisions::check_rect_circle(other.min, other.max, self.position, self.radius)
             }
             Hitbox::Group(other) => other.collides_with(&self.as_hitbox()),
-            Hitbox::Polygon(other) => other.collides_with(&self.as_rectangle().as_hitbox()),
+            Hitbox::Polygon(other) => collisions::check_circle_polygon(&other.points, self.position, self.radius),
+            Hitbox::Line(other) => other.collides_with(&self.as_hitbox()),
+            Hitbox::Capsule(other) => other.collides_with(&self.as_hitbox()),
         }
     }
 
@@ -67,14 +374,14 @@ impl Collidable for CircleHitbox {
                 if let Some(collision) =
                     intersections::circles(self.position, self.radius, other.position, other.radius)
                 {
-                    self.position = self.position - (collision.dir * collision.pen)
+                    self.position = (self.position - collision.dir * collision.pen).sanitize(self.position)
                 }
             }
             Hitbox::Rect(other) => {
                 if let Some(collision) =
                     intersections::rect_circle(other.min, other.max, self.position, self.radius)
                 {
-                    self.position = self.position - (collision.dir * collision.pen)
+                    self.position = (self.position - collision.dir * collision.pen).sanitize(self.position)
                 }
             }
             Hitbox::Group(other) => {
@@ -128,6 +435,10 @@ impl Collidable for CircleHitbox {
         random_point_in_circle(self.position, None, self.radius)
     }
 
+    fn random_perimeter_point(&self, rng: &mut GameRng) -> Vec2D {
+        self.position + Vec2D::from_polar(rng.gen_range(0.0..TAU), Some(self.radius))
+    }
+
     fn as_rectangle(&self) -> RectangleHitbox {
         RectangleHitbox {
             min: Vec2D {
@@ -141,18 +452,33 @@ impl Collidable for CircleHitbox {
         }
     }
 
+    fn bounding_circle(&self) -> CircleHitbox {
+        self.clone()
+    }
+
     fn is_vec_inside(&self, vec: Vec2D) -> bool {
-        geometry::distance(vec, self.position) < self.radius
+        vec.distance_to(self.position) < self.radius
+    }
+
+    fn overlaps_rect(&self, min: Vec2D, max: Vec2D) -> bool {
+        collisions::check_rect_circle(min, max, self.position, self.radius)
     }
 
     fn get_center(&self) -> Vec2D {
         self.position
     }
+
+    fn area(&self) -> f64 {
+        PI * self.radius * self.radius
+    }
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RectangleHitbox {
+    #[cfg_attr(feature = "serde", serde(with = "vec_pair"))]
     min: Vec2D,
+    #[cfg_attr(feature = "serde", serde(with = "vec_pair"))]
     max: Vec2D,
 }
 
@@ -172,13 +498,39 @@ impl RectangleHitbox {
 
     pub fn from_rect(width: f64, height: f64, center: Option<Vec2D>) -> RectangleHitbox {
         let size = Vec2D::new(width / 2.0, height / 2.0);
-        let center = center.unwrap_or(Vec2D::new(0.0, 0.0));
+        let center = center.unwrap_or(Vec2D::zero());
 
         RectangleHitbox {
             min: center - size,
             max: center + size,
         }
     }
+
+    /// Returns the rectangle's four corners, going clockwise from `min`
+    pub fn corners(&self) -> [Vec2D; 4] {
+        [
+            self.min,
+            Vec2D::new(self.max.x, self.min.y),
+            self.max,
+            Vec2D::new(self.min.x, self.max.y),
+        ]
+    }
+
+    pub fn min(&self) -> Vec2D {
+        self.min
+    }
+
+    pub fn max(&self) -> Vec2D {
+        self.max
+    }
+
+    pub fn width(&self) -> f64 {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> f64 {
+        self.max.y - self.min.y
+    }
 }
 
 impl Collidable for RectangleHitbox {
@@ -196,6 +548,8 @@ impl Collidable for RectangleHitbox {
             }
             Hitbox::Polygon(other) => other.collides_with(&self.as_hitbox()),
             Hitbox::Group(other) => other.collides_with(&self.as_hitbox()),
+            Hitbox::Line(other) => other.collides_with(&self.as_hitbox()),
+            Hitbox::Capsule(other) => other.collides_with(&self.as_hitbox()),
         }
     }
 
@@ -205,7 +559,8 @@ impl Collidable for RectangleHitbox {
                 if let Some(collision) =
                     intersections::rect_circle(self.min, self.max, other.position, other.radius)
                 {
-                    let rect = self.transform(collision.dir * -collision.pen, None, None);
+                    let offset = (collision.dir * -collision.pen).sanitize(Vec2D::zero());
+                    let rect = self.transform(offset, None, None);
                     self.max = rect.max;
                     self.min = rect.min;
                 }
@@ -213,7 +568,8 @@ impl Collidable for RectangleHitbox {
             Hitbox::Rect(other) => {
                 if let Some(collision) = intersections::rects(self.min, self.max, other.min, other.max)
                 {
-                    let rect = self.transform(collision.dir * -collision.pen, None, None);
+                    let offset = (collision.dir * -collision.pen).sanitize(Vec2D::zero());
+                    let rect = self.transform(offset, None, None);
                     self.min = rect.min;
                     self.max = rect.max
                 }
@@ -282,14 +638,26 @@ impl Collidable for RectangleHitbox {
         }
     }
 
+    fn random_perimeter_point(&self, rng: &mut GameRng) -> Vec2D {
+        random_outline_point(&self.corners(), rng)
+    }
+
     fn as_rectangle(&self) -> RectangleHitbox {
         self.clone()
     }
 
+    fn bounding_circle(&self) -> CircleHitbox {
+        CircleHitbox::new(self.get_center(), self.min.distance_to(self.max) / 2.0)
+    }
+
     fn is_vec_inside(&self, vec: Vec2D) -> bool {
         vec.x > self.min.x && vec.y > self.min.y && vec.x < self.max.x && vec.y < self.max.y
     }
 
+    fn overlaps_rect(&self, min: Vec2D, max: Vec2D) -> bool {
+        collisions::check_rects(self.min, self.max, min, max)
+    }
+
     fn get_center(&self) -> Vec2D {
         Vec2D {
             x: self.min.x + ((self.max.x - self.min.x) / 2.0_f64),
@@ -297,6 +665,10 @@ impl Collidable for RectangleHitbox {
         }
     }
 
+    fn area(&self) -> f64 {
+        self.width() * self.height()
+    }
+
     fn panic_unknown_subclass(other: &Hitbox) {
         panic!(
             "Hitbox type RectangleHitbox doesn't support this operation with hitbox type {:#?}",
@@ -306,148 +678,640 @@ impl Collidable for RectangleHitbox {
 }
 
 #[derive(Debug, Clone)]
-pub struct PolygonHitbox {}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolygonHitbox {
+    #[cfg_attr(feature = "serde", serde(with = "vec_pairs"))]
+    points: Vec<Vec2D>,
+}
+
+impl PolygonHitbox {
+    pub fn new(points: Vec<Vec2D>) -> PolygonHitbox {
+        assert!(points.len() >= 3, "A polygon needs at least 3 points, got {}", points.len());
+        PolygonHitbox { points }
+    }
+
+    pub fn points(&self) -> &[Vec2D] {
+        &self.points
+    }
+}
 
 impl Collidable for PolygonHitbox {
     fn as_hitbox(&self) -> Hitbox {
-        todo!()
+        Hitbox::Polygon(self.clone())
     }
 
     fn collides_with(&self, other: &Hitbox) -> bool {
-        todo!()
+        match other {
+            Hitbox::Rect(other) => {
+                self.points.iter().any(|point| other.is_vec_inside(*point))
+                    || other.corners().iter().any(|corner| self.is_vec_inside(*corner))
+                    || self.edges().any(|(start, end)| {
+                        intersections::line_rect_test(start, end, other.min, other.max)
+                    })
+            }
+            Hitbox::Circle(other) => collisions::check_circle_polygon(&self.points, other.position, other.radius),
+            // SAT assumes convex polygons; concave input may report false positives
+            Hitbox::Polygon(other) => collisions::check_polygons(&self.points, &other.points),
+            Hitbox::Group(other) => other.collides_with(&self.as_hitbox()),
+            Hitbox::Line(other) => other.collides_with(&self.as_hitbox()),
+            Hitbox::Capsule(other) => other.collides_with(&self.as_hitbox()),
+        }
     }
 
     fn resolve_collision(&mut self, other: &mut Hitbox) {
-        todo!()
+        PolygonHitbox::panic_unknown_subclass(other)
     }
 
     fn distance_to(&self, other: &Hitbox) -> Option<CollisionRecord> {
-        todo!()
+        PolygonHitbox::panic_unknown_subclass(other);
+        None
     }
 
     fn transform(&self, pos: Vec2D, scale: Option<f64>, orientation: Option<Orientation>) -> Self {
-        todo!()
+        let scale = scale.unwrap_or(1.0);
+        let angle = orientation.unwrap_or(Orientation::Up).to_angle();
+        let origin = Vec2D::zero();
+
+        // equivalent to `add_adjust`-ing each point to `pos` and then scaling it
+        PolygonHitbox {
+            points: self.points.iter()
+                .map(|point| point.transform(origin, scale, angle, pos * scale))
+                .collect(),
+        }
     }
 
     fn scale(&mut self, scale: f64) {
-        todo!()
+        let center = self.get_center();
+        for point in self.points.iter_mut() {
+            *point = center + (*point - center) * scale;
+        }
     }
 
     fn intersects_line(&self, a: Vec2D, b: Vec2D) -> Option<IntersectionResponse> {
-        todo!()
+        let mut closest: Option<IntersectionResponse> = None;
+        let mut closest_dist = f64::MAX;
+
+        for (start, end) in self.edges() {
+            if let Some(point) = intersections::line_line(a, b, start, end) {
+                let dist = a.distance_squared_to(point);
+                if dist < closest_dist {
+                    let edge = end - start;
+                    let mut normal = edge.perpendicular().normalize(None);
+                    // make the normal face the start of the line
+                    if normal.dot(a - point) < 0.0 {
+                        normal = -normal;
+                    }
+
+                    closest_dist = dist;
+                    closest = Some(IntersectionResponse { point, normal });
+                }
+            }
+        }
+
+        closest
     }
 
     fn random_point(&self) -> Vec2D {
-        todo!()
+        let rect = self.as_rectangle();
+        loop {
+            let point = rect.random_point();
+            if self.is_vec_inside(point) {
+                return point;
+            }
+        }
+    }
+
+    fn random_perimeter_point(&self, rng: &mut GameRng) -> Vec2D {
+        random_outline_point(&self.points, rng)
     }
 
     fn as_rectangle(&self) -> RectangleHitbox {
-        todo!()
+        let mut min = Vec2D::new(f64::INFINITY, f64::INFINITY);
+        let mut max = Vec2D::new(f64::NEG_INFINITY, f64::NEG_INFINITY);
+
+        for point in self.points.iter() {
+            min = min.min_components(*point);
+            max = max.max_components(*point);
+        }
+
+        RectangleHitbox {
+            min,
+            max
+        }
+    }
+
+    fn bounding_circle(&self) -> CircleHitbox {
+        let center = self.get_center();
+        let radius = self.points.iter().map(|point| point.distance_to(center)).fold(0.0, f64::max);
+        CircleHitbox::new(center, radius)
     }
 
     fn is_vec_inside(&self, vec: Vec2D) -> bool {
-        todo!()
+        geometry::point_in_polygon(vec, &self.points)
+    }
+
+    fn overlaps_rect(&self, min: Vec2D, max: Vec2D) -> bool {
+        let bounds = self.as_rectangle();
+        if !collisions::check_rects(bounds.min, bounds.max, min, max) {
+            return false;
+        }
+
+        self.collides_with(&RectangleHitbox { min, max }.as_hitbox())
     }
 
     fn get_center(&self) -> Vec2D {
-        todo!()
+        geometry::polygon_centroid(&self.points)
+    }
+
+    fn area(&self) -> f64 {
+        geometry::polygon_area(&self.points)
     }
 
     fn panic_unknown_subclass(other: &Hitbox) {
-        todo!()
+        panic!(
+            "Hitbox type PolygonHitbox doesn't support this operation with hitbox type {:#?}",
+            other
+        );
+    }
+}
+
+impl PolygonHitbox {
+    /// Iterates over the polygon's edges as `(start, end)` pairs, including the
+    /// closing edge going from the last point back to the first
+    fn edges(&self) -> impl Iterator<Item = (Vec2D, Vec2D)> + '_ {
+        let len = self.points.len();
+        (0..len).map(move |i| (self.points[i], self.points[(i + 1) % len]))
     }
 }
 
+/// A line segment, for thin obstacles like fences and walls. It has no inside:
+/// points are never inside it, and its area is 0
 #[derive(Debug, Clone)]
-pub struct GroupHitbox {
-    hitboxes: Vec<Hitbox>,
-    position: Vec2D,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineHitbox {
+    #[cfg_attr(feature = "serde", serde(with = "vec_pair"))]
+    start: Vec2D,
+    #[cfg_attr(feature = "serde", serde(with = "vec_pair"))]
+    end: Vec2D,
 }
 
-impl GroupHitbox {
-    pub fn new(hitboxes: Vec<Hitbox>) -> GroupHitbox {
-        GroupHitbox {
-            hitboxes,
-            position: Vec2D::new(0.0, 0.0)
-        }
+impl LineHitbox {
+    pub fn new(start: Vec2D, end: Vec2D) -> LineHitbox {
+        LineHitbox { start, end }
+    }
+
+    pub fn start(&self) -> Vec2D {
+        self.start
+    }
+
+    pub fn end(&self) -> Vec2D {
+        self.end
     }
 }
 
-impl Collidable for GroupHitbox {
+impl Collidable for LineHitbox {
     fn as_hitbox(&self) -> Hitbox {
-        todo!()
+        Hitbox::Line(self.clone())
     }
+
     fn collides_with(&self, other: &Hitbox) -> bool {
-        self.hitboxes.iter().any(|hitbox| match hitbox {
-            Hitbox::Circle(hitbox) => hitbox.collides_with(other),
-            Hitbox::Rect(hitbox) => hitbox.collides_with(other),
-            Hitbox::Polygon(hitbox) => hitbox.collides_with(other),
-            Hitbox::Group(hitbox) => hitbox.collides_with(other),
-        })
+        match other {
+            Hitbox::Circle(other) => {
+                distances::to_line(other.position, self.start, self.end) < other.radius * other.radius
+            }
+            Hitbox::Rect(other) => intersections::line_rect_test(self.start, self.end, other.min, other.max),
+            Hitbox::Line(other) => intersections::line_line(self.start, self.end, other.start, other.end).is_some(),
+            Hitbox::Polygon(other) => {
+                other.is_vec_inside(self.start)
+                    || other.edges().any(|(start, end)| intersections::line_line(self.start, self.end, start, end).is_some())
+            }
+            Hitbox::Group(other) => other.collides_with(&self.as_hitbox()),
+            Hitbox::Capsule(other) => other.collides_with(&self.as_hitbox()),
+        }
     }
 
     fn resolve_collision(&mut self, other: &mut Hitbox) {
+        LineHitbox::panic_unknown_subclass(other)
+    }
+
+    fn distance_to(&self, other: &Hitbox) -> Option<CollisionRecord> {
         match other {
-            Hitbox::Circle(other) => other.resolve_collision(&mut self.as_hitbox()),
-            Hitbox::Rect(other) => other.resolve_collision(&mut self.as_hitbox()),
-            Hitbox::Polygon(other) => other.resolve_collision(&mut self.as_hitbox()),
-            Hitbox::Group(other) => other.resolve_collision(&mut self.as_hitbox()),
+            Hitbox::Circle(other) => Some(distances::circle_line(self.start, self.end, other.position, other.radius)),
+            _ => {
+                LineHitbox::panic_unknown_subclass(other);
+                None
+            }
+        }
+    }
+
+    fn transform(&self, pos: Vec2D, scale: Option<f64>, orientation: Option<Orientation>) -> Self {
+        let scale = scale.unwrap_or(1.0);
+        let angle = orientation.unwrap_or(Orientation::Up).to_angle();
+        let origin = Vec2D::zero();
+
+        LineHitbox {
+            start: self.start.transform(origin, scale, angle, pos * scale),
+            end: self.end.transform(origin, scale, angle, pos * scale),
+        }
+    }
+
+    fn scale(&mut self, scale: f64) {
+        let center = self.get_center();
+        self.start = center + (self.start - center) * scale;
+        self.end = center + (self.end - center) * scale;
+    }
+
+    fn intersects_line(&self, a: Vec2D, b: Vec2D) -> Option<IntersectionResponse> {
+        let point = intersections::line_line(a, b, self.start, self.end)?;
+        let mut normal = (self.end - self.start).perpendicular().normalize(None);
+        // make the normal face the start of the line
+        if normal.dot(a - point) < 0.0 {
+            normal = -normal;
         }
+
+        Some(IntersectionResponse { point, normal })
+    }
+
+    fn random_point(&self) -> Vec2D {
+        self.start.lerp(self.end, random_float(0.0, 1.0))
+    }
+
+    fn random_perimeter_point(&self, rng: &mut GameRng) -> Vec2D {
+        self.start.lerp(self.end, rng.gen_range(0.0..=1.0))
+    }
+
+    fn as_rectangle(&self) -> RectangleHitbox {
+        RectangleHitbox::from_line(self.start, self.end)
+    }
+
+    fn bounding_circle(&self) -> CircleHitbox {
+        CircleHitbox::new(self.get_center(), self.start.distance_to(self.end) / 2.0)
+    }
+
+    fn is_vec_inside(&self, _vec: Vec2D) -> bool {
+        false
+    }
+
+    fn overlaps_rect(&self, min: Vec2D, max: Vec2D) -> bool {
+        intersections::line_rect_test(self.start, self.end, min, max)
+    }
+
+    fn get_center(&self) -> Vec2D {
+        self.start.lerp(self.end, 0.5)
+    }
+
+    fn area(&self) -> f64 {
+        0.0
+    }
+
+    fn panic_unknown_subclass(other: &Hitbox) {
+        panic!(
+            "Hitbox type LineHitbox doesn't support this operation with hitbox type {:#?}",
+            other
+        );
+    }
+}
+
+/// A line segment grown by a radius, i.e. the shape a circle sweeps when moving from
+/// `start` to `end`; the usual hitbox for a bullet's travel in one tick
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CapsuleHitbox {
+    #[cfg_attr(feature = "serde", serde(with = "vec_pair"))]
+    start: Vec2D,
+    #[cfg_attr(feature = "serde", serde(with = "vec_pair"))]
+    end: Vec2D,
+    radius: f64,
+}
+
+impl CapsuleHitbox {
+    pub fn new(start: Vec2D, end: Vec2D, radius: f64) -> CapsuleHitbox {
+        CapsuleHitbox { start, end, radius }
+    }
+
+    pub fn start(&self) -> Vec2D {
+        self.start
+    }
+
+    pub fn end(&self) -> Vec2D {
+        self.end
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// Approximates this capsule with a polygon, each rounded end getting half of `segments`
+    pub fn to_polygon(&self, segments: usize) -> PolygonHitbox {
+        assert!(segments >= 2, "A capsule needs at least 2 segments, got {}", segments);
+
+        let half = segments / 2;
+        let step = PI / half as f64;
+        let angle = (self.end - self.start).direction();
+        let cap = |center: Vec2D, from: f64| {
+            (0..=half).map(move |i| center + Vec2D::from_polar(from + step * i as f64, Some(self.radius)))
+        };
+
+        PolygonHitbox::new(
+            cap(self.end, angle - PI / 2.0)
+                .chain(cap(self.start, angle + PI / 2.0))
+                .collect()
+        )
+    }
+
+    /// The squared distance between this capsule's segment and a rectangle, 0 if they touch
+    fn rect_distance_squared(&self, min: Vec2D, max: Vec2D) -> f64 {
+        if intersections::line_rect_test(self.start, self.end, min, max) {
+            return 0.0;
+        }
+
+        let rect = RectangleHitbox { min, max };
+        [self.start, self.end]
+            .iter()
+            .map(|point| geometry::closest_point_on_rect(min, max, *point).distance_squared_to(*point))
+            .chain(rect.corners().iter().map(|corner| distances::to_line(*corner, self.start, self.end)))
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
+impl Collidable for CapsuleHitbox {
+    fn as_hitbox(&self) -> Hitbox {
+        Hitbox::Capsule(self.clone())
+    }
+
+    fn collides_with(&self, other: &Hitbox) -> bool {
+        let within = |distance_squared: f64, radius: f64| distance_squared < radius * radius;
+
+        match other {
+            Hitbox::Circle(other) => within(
+                distances::to_line(other.position, self.start, self.end),
+                self.radius + other.radius,
+            ),
+            Hitbox::Rect(other) => within(self.rect_distance_squared(other.min, other.max), self.radius),
+            Hitbox::Line(other) => within(
+                distances::segments(self.start, self.end, other.start, other.end),
+                self.radius,
+            ),
+            Hitbox::Capsule(other) => within(
+                distances::segments(self.start, self.end, other.start, other.end),
+                self.radius + other.radius,
+            ),
+            Hitbox::Polygon(other) => {
+                other.is_vec_inside(self.start)
+                    || other.edges().any(|(start, end)| {
+                        within(distances::segments(self.start, self.end, start, end), self.radius)
+                    })
+            }
+            Hitbox::Group(other) => other.collides_with(&self.as_hitbox()),
+        }
+    }
+
+    fn resolve_collision(&mut self, other: &mut Hitbox) {
+        CapsuleHitbox::panic_unknown_subclass(other)
     }
 
     fn distance_to(&self, other: &Hitbox) -> Option<CollisionRecord> {
-        let mut distance = f64::MAX;
-        let mut record = CollisionRecord {
-            collided: false,
-            distance: f64::MAX
+        let record = |distance_squared: f64, radius: f64| CollisionRecord {
+            collided: distance_squared < radius * radius,
+            distance: distance_squared - radius * radius,
         };
 
-        for hitbox in self.hitboxes.iter() {
-            let new_record: CollisionRecord;
+        match other {
+            Hitbox::Circle(other) => Some(record(
+                distances::to_line(other.position, self.start, self.end),
+                self.radius + other.radius,
+            )),
+            Hitbox::Rect(other) => Some(record(self.rect_distance_squared(other.min, other.max), self.radius)),
+            _ => {
+                CapsuleHitbox::panic_unknown_subclass(other);
+                None
+            }
+        }
+    }
+
+    fn transform(&self, pos: Vec2D, scale: Option<f64>, orientation: Option<Orientation>) -> Self {
+        let scale = scale.unwrap_or(1.0);
+        let angle = orientation.unwrap_or(Orientation::Up).to_angle();
+        let origin = Vec2D::zero();
+
+        CapsuleHitbox {
+            start: self.start.transform(origin, scale, angle, pos * scale),
+            end: self.end.transform(origin, scale, angle, pos * scale),
+            radius: self.radius * scale,
+        }
+    }
+
+    fn scale(&mut self, scale: f64) {
+        let center = self.get_center();
+        self.start = center + (self.start - center) * scale;
+        self.end = center + (self.end - center) * scale;
+        self.radius *= scale;
+    }
+
+    fn intersects_line(&self, a: Vec2D, b: Vec2D) -> Option<IntersectionResponse> {
+        let offset = (self.end - self.start).perpendicular().normalize(None) * self.radius;
+
+        let caps = [self.start, self.end]
+            .into_iter()
+            .filter_map(|center| intersections::line_circle(a, b, center, self.radius));
+        let sides = [offset, -offset].into_iter().filter_map(|offset| {
+            intersections::line_line(a, b, self.start + offset, self.end + offset).map(|point| IntersectionResponse {
+                point,
+                normal: offset.normalize(None),
+            })
+        });
 
+        caps.chain(sides)
+            .min_by(|c, d| c.point.distance_squared_to(a).total_cmp(&d.point.distance_squared_to(a)))
+    }
+
+    /// Picks the body or the caps by their area, then a point inside that part
+    fn random_point(&self) -> Vec2D {
+        let length = self.start.distance_to(self.end);
+        let body = 2.0 * self.radius * length;
+        let caps = PI * self.radius * self.radius;
+
+        // a capsule without a radius is just its segment
+        if body + caps <= 0.0 {
+            return self.start.lerp(self.end, random_float(0.0, 1.0));
+        }
+
+        if random_float(0.0, body + caps) < body {
+            let direction = (self.end - self.start).normalize(None);
+            self.start
+                + direction * random_float(0.0, length)
+                + direction.perpendicular() * random_float(-self.radius, self.radius)
+        } else {
+            // together, the caps make up a full circle: points on the end's side of it belong to the end cap
+            let offset = random_point_in_circle(Vec2D::zero(), None, self.radius);
+            if offset.dot(self.end - self.start) > 0.0 {
+                self.end + offset
+            } else {
+                self.start + offset
+            }
+        }
+    }
+
+    fn random_perimeter_point(&self, rng: &mut GameRng) -> Vec2D {
+        let length = self.start.distance_to(self.end);
+        let direction = (self.end - self.start).normalize(None);
+        let offset = direction.perpendicular() * self.radius;
+
+        // two straight sides, then two half circles
+        let pick = rng.gen_range(0.0..(2.0 * length + TAU * self.radius));
+        if pick < length {
+            self.start + offset + direction * pick
+        } else if pick < 2.0 * length {
+            self.end - offset - direction * (pick - length)
+        } else {
+            let angle = (pick - 2.0 * length) / self.radius;
+            let center = if angle < PI { self.end } else { self.start };
+            center + (-offset).rotate(angle)
+        }
+    }
+
+    fn as_rectangle(&self) -> RectangleHitbox {
+        let radius = Vec2D::new(self.radius, self.radius);
+        RectangleHitbox {
+            min: self.start.min_components(self.end) - radius,
+            max: self.start.max_components(self.end) + radius,
+        }
+    }
+
+    fn bounding_circle(&self) -> CircleHitbox {
+        CircleHitbox::new(self.get_center(), self.start.distance_to(self.end) / 2.0 + self.radius)
+    }
+
+    fn is_vec_inside(&self, vec: Vec2D) -> bool {
+        distances::to_line(vec, self.start, self.end) < self.radius * self.radius
+    }
+
+    fn overlaps_rect(&self, min: Vec2D, max: Vec2D) -> bool {
+        self.rect_distance_squared(min, max) < self.radius * self.radius
+    }
+
+    fn get_center(&self) -> Vec2D {
+        self.start.lerp(self.end, 0.5)
+    }
+
+    fn area(&self) -> f64 {
+        2.0 * self.radius * self.start.distance_to(self.end) + PI * self.radius * self.radius
+    }
+
+    fn panic_unknown_subclass(other: &Hitbox) {
+        panic!(
+            "Hitbox type CapsuleHitbox doesn't support this operation with hitbox type {:#?}",
+            other
+        );
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupHitbox {
+    hitboxes: Vec<Hitbox>,
+    /// Where the group was last placed with `transform`; the origin until then
+    #[cfg_attr(feature = "serde", serde(with = "vec_pair", default = "Vec2D::zero"))]
+    position: Vec2D,
+    /// Bounding box of all the members, computed on the first call to `as_rectangle`.
+    /// Anything changing the members must reset it with `take`. A `OnceLock` rather than
+    /// a `RefCell` so that hitboxes stay `Sync` and can be shared between threads
+    #[cfg_attr(feature = "serde", serde(skip))]
+    bounds: OnceLock<RectangleHitbox>,
+}
+
+impl GroupHitbox {
+    pub fn new(hitboxes: Vec<Hitbox>) -> GroupHitbox {
+        GroupHitbox {
+            hitboxes,
+            position: Vec2D::zero(),
+            bounds: OnceLock::new(),
+        }
+    }
+
+    pub fn push(&mut self, hitbox: Hitbox) {
+        self.bounds.take();
+        self.hitboxes.push(hitbox);
+    }
+
+    /// Removes the member at `index`, shifting the ones after it down.
+    /// Returns `None` if there is no such member
+    pub fn remove(&mut self, index: usize) -> Option<Hitbox> {
+        if index >= self.hitboxes.len() {
+            return None;
+        }
+        self.bounds.take();
+        Some(self.hitboxes.remove(index))
+    }
+
+    pub fn position(&self) -> Vec2D {
+        self.position
+    }
+
+    pub fn children(&self) -> &[Hitbox] {
+        &self.hitboxes
+    }
+
+    pub fn len(&self) -> usize {
+        self.hitboxes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hitboxes.is_empty()
+    }
+
+    /// Computes the bounding box of all the members, bypassing the cache
+    fn compute_bounds(&self) -> RectangleHitbox {
+        let mut min = Vec2D::new(f64::INFINITY, f64::INFINITY);
+        let mut max = Vec2D::new(f64::NEG_INFINITY, f64::NEG_INFINITY);
+
+        fn update<T: Collidable>(hitbox: &T, min: &mut Vec2D, max: &mut Vec2D) {
+            let rect = hitbox.as_rectangle();
+            *min = min.min_components(rect.min);
+            *max = max.max_components(rect.max);
+        }
+
+        for hitbox in self.hitboxes.iter() {
             match hitbox {
-                Hitbox::Circle(hitbox) => {
-                    match other {
-                        Hitbox::Circle(other) => {
-                            new_record = distances::circles(other.position, other.radius, hitbox.position, hitbox.radius);
-                        },
-                        Hitbox::Rect(other) => {
-                            new_record = distances::circle_rect(other.min, other.max, hitbox.position, hitbox.radius);
-                        },
-                        _ => {
-                            Self::panic_unknown_subclass(other);
-                            return None;
-                        }
-                    }
-                },
-                Hitbox::Rect(hitbox) => {
-                    match  other {
-                        Hitbox::Circle(other) => {
-                            new_record = distances::circle_rect(hitbox.min, hitbox.max, other.position, other.radius);
-                        },
-                        Hitbox::Rect(other) => {
-                            new_record = distances::rects(other.min, other.max, hitbox.min, hitbox.max)
-                        },
-                        _ => {
-                            Self::panic_unknown_subclass(other);
-                            return None;
-                        }
-                    }
-                },
-                _ => {
-                    Self::panic_unknown_subclass(hitbox);
-                    return None;
-                }
+                Hitbox::Circle(hitbox) => update(hitbox, &mut min, &mut max),
+                Hitbox::Rect(hitbox) => update(hitbox, &mut min, &mut max),
+                Hitbox::Polygon(hitbox) => update(hitbox, &mut min, &mut max),
+                Hitbox::Group(hitbox) => update(hitbox, &mut min, &mut max),
+                Hitbox::Line(hitbox) => update(hitbox, &mut min, &mut max),
+                Hitbox::Capsule(hitbox) => update(hitbox, &mut min, &mut max),
             }
+        }
 
-            if new_record.distance < distance {
-                record = new_record;
-                distance = new_record.distance;
+        RectangleHitbox {
+            min,
+            max
+        }
+    }
+}
+
+impl Collidable for GroupHitbox {
+    fn as_hitbox(&self) -> Hitbox {
+        Hitbox::Group(self.clone())
+    }
+    fn collides_with(&self, other: &Hitbox) -> bool {
+        self.hitboxes.iter().any(|hitbox| hitboxes_collide(hitbox, other))
+    }
+
+    /// Unlike the other hitboxes, a group stays where it is: `other` is the one pushed out of every
+    /// member it overlaps. Use `resolve_hitbox_collision` to move the group instead
+    fn resolve_collision(&mut self, other: &mut Hitbox) {
+        for hitbox in self.hitboxes.iter_mut() {
+            if hitboxes_collide(other, hitbox) {
+                resolve_hitbox_collision(other, hitbox);
             }
         }
+    }
 
-        //TODO: I don't know if this is the right way to deal with this.
-        Some(record)
+    fn distance_to(&self, other: &Hitbox) -> Option<CollisionRecord> {
+        self.hitboxes
+            .iter()
+            .filter_map(|hitbox| hitbox_distance(hitbox, other))
+            .min_by(|c, d| c.distance.total_cmp(&d.distance))
     }
 
     fn transform(&self, pos: Vec2D, scale: Option<f64>, orientation: Option<Orientation>) -> Self {
@@ -458,20 +1322,26 @@ impl Collidable for GroupHitbox {
                     Hitbox::Rect(rect) => Hitbox::Rect(rect.transform(pos, scale, orientation)),
                     Hitbox::Polygon(polygon) => Hitbox::Polygon(polygon.transform(pos, scale, orientation)),
                     Hitbox::Group(group) => Hitbox::Group(group.transform(pos, scale, orientation)),
+                    Hitbox::Line(line) => Hitbox::Line(line.transform(pos, scale, orientation)),
+                    Hitbox::Capsule(capsule) => Hitbox::Capsule(capsule.transform(pos, scale, orientation)),
                 }
             }).collect(),
             position: pos,
+            bounds: OnceLock::new(),
         }
     }
 
 
     fn scale(&mut self, scale: f64) {
+        self.bounds.take();
         for hitbox in self.hitboxes.iter_mut() {
             match hitbox {
                 Hitbox::Circle(hitbox) => hitbox.scale(scale),
                 Hitbox::Rect(hitbox) => hitbox.scale(scale),
                 Hitbox::Polygon(hitbox) => hitbox.scale(scale),
                 Hitbox::Group(hitbox) => hitbox.scale(scale),
+                Hitbox::Line(hitbox) => hitbox.scale(scale),
+                Hitbox::Capsule(hitbox) => hitbox.scale(scale),
             }
         }
     }
@@ -486,52 +1356,57 @@ impl Collidable for GroupHitbox {
                 Hitbox::Rect(hitbox) => hitbox.intersects_line(a, b),
                 Hitbox::Polygon(hitbox) => hitbox.intersects_line(a, b),
                 Hitbox::Group(hitbox) => hitbox.intersects_line(a, b),
+                Hitbox::Line(hitbox) => hitbox.intersects_line(a, b),
+                Hitbox::Capsule(hitbox) => hitbox.intersects_line(a, b),
             } {
                 intersections.push(intersection);
             }
         }
 
         intersections.sort_by(|c, d| {
-            geometry::distance_squared(c.point, a).partial_cmp(&geometry::distance_squared(d.point, a)).unwrap()
+            c.point.distance_squared_to(a).partial_cmp(&d.point.distance_squared_to(a)).unwrap()
         });
 
         intersections.first().cloned()
     }
 
+    /// Picks a child with a probability proportional to its area, so that points are spread
+    /// evenly over the whole group rather than over its children
     fn random_point(&self) -> Vec2D {
-        match random_item(&self.hitboxes) {
+        let mut weights: Vec<f64> = self.hitboxes.iter().map(hitbox_area).collect();
+        // members without any area (like lines) can't be weighed by it; if that's all there is, pick uniformly
+        if !weights.iter().any(|area| *area > 0.0) {
+            weights.fill(1.0);
+        }
+
+        match weighted_random(&self.hitboxes, &weights) {
             Hitbox::Circle(hitbox) => hitbox.random_point(),
             Hitbox::Rect(hitbox) => hitbox.random_point(),
             Hitbox::Polygon(hitbox) => hitbox.random_point(),
             Hitbox::Group(hitbox) => hitbox.random_point(),
+            Hitbox::Line(hitbox) => hitbox.random_point(),
+            Hitbox::Capsule(hitbox) => hitbox.random_point(),
         }
     }
 
-    fn as_rectangle(&self) -> RectangleHitbox {
-        let mut min = Vec2D::new(f64::MAX, f64::MAX);
-        let mut max = Vec2D::new(0.0, 0.0);
-
-        fn update<T: Collidable>(hitbox: &T, min: &mut Vec2D, max: &mut Vec2D) {
-            let rect = hitbox.as_rectangle();
-            min.x = min.x.min(rect.min.x);
-            min.y = min.y.min(rect.min.y);
-            max.x = max.x.max(rect.max.x);
-            max.y = max.y.max(rect.max.y);
+    fn random_perimeter_point(&self, rng: &mut GameRng) -> Vec2D {
+        match self.hitboxes.choose(rng).expect("Cannot pick a point on an empty group") {
+            Hitbox::Circle(hitbox) => hitbox.random_perimeter_point(rng),
+            Hitbox::Rect(hitbox) => hitbox.random_perimeter_point(rng),
+            Hitbox::Polygon(hitbox) => hitbox.random_perimeter_point(rng),
+            Hitbox::Group(hitbox) => hitbox.random_perimeter_point(rng),
+            Hitbox::Line(hitbox) => hitbox.random_perimeter_point(rng),
+            Hitbox::Capsule(hitbox) => hitbox.random_perimeter_point(rng),
         }
+    }
 
-        for hitbox in self.hitboxes.iter() {
-            match hitbox {
-                Hitbox::Circle(hitbox) => update(hitbox, &mut min, &mut max),
-                Hitbox::Rect(hitbox) => update(hitbox, &mut min, &mut max),
-                Hitbox::Polygon(hitbox) => update(hitbox, &mut min, &mut max),
-                Hitbox::Group(hitbox) => update(hitbox, &mut min, &mut max),
-            }
-        }
+    fn as_rectangle(&self) -> RectangleHitbox {
+        self.bounds.get_or_init(|| self.compute_bounds()).clone()
+    }
 
-        RectangleHitbox {
-            min,
-            max
-        }
+    /// Encloses the group's bounding box, which isn't always the tightest fit
+    fn bounding_circle(&self) -> CircleHitbox {
+        self.as_rectangle().bounding_circle()
     }
 
     // TODO Test this function thouroughly cuz idk if it works.
@@ -542,16 +1417,34 @@ impl Collidable for GroupHitbox {
                 Hitbox::Rect(hitbox) => if hitbox.is_vec_inside(vec) {return true;},
                 Hitbox::Polygon(hitbox) => if hitbox.is_vec_inside(vec) {return true;},
                 Hitbox::Group(hitbox) => if hitbox.is_vec_inside(vec) {return true;},
+                Hitbox::Line(hitbox) => if hitbox.is_vec_inside(vec) {return true;},
+                Hitbox::Capsule(hitbox) => if hitbox.is_vec_inside(vec) {return true;},
             }
         }
 
         false
     }
 
+    fn overlaps_rect(&self, min: Vec2D, max: Vec2D) -> bool {
+        self.hitboxes.iter().any(|hitbox| match hitbox {
+            Hitbox::Circle(hitbox) => hitbox.overlaps_rect(min, max),
+            Hitbox::Rect(hitbox) => hitbox.overlaps_rect(min, max),
+            Hitbox::Polygon(hitbox) => hitbox.overlaps_rect(min, max),
+            Hitbox::Group(hitbox) => hitbox.overlaps_rect(min, max),
+            Hitbox::Line(hitbox) => hitbox.overlaps_rect(min, max),
+            Hitbox::Capsule(hitbox) => hitbox.overlaps_rect(min, max),
+        })
+    }
+
     fn get_center(&self) -> Vec2D {
         self.as_rectangle().get_center()
     }
 
+    /// The summed area of all the children; overlapping parts are counted more than once
+    fn area(&self) -> f64 {
+        self.hitboxes.iter().map(hitbox_area).sum()
+    }
+
     fn panic_unknown_subclass(other: &Hitbox) {
         panic!(
             "Hitbox type GroupHitbox doesn't support this operation with hitbox type {:#?}",
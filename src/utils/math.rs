@@ -23,17 +23,21 @@ pub struct IntersectionResponse {
 }
 
 pub mod numeric {
+    /// Returns `1` for positive numbers, `-1` for negative ones and `0` for zero
+    /// (either sign) and NaN; `inverse` swaps the two non-zero results
     pub fn get_sign(number: f64, inverse: bool /* <- wtf?? */) -> i8 {
-        if inverse {
-            if number > 0.0 {
-                -1
-            } else {
-                1
-            }
-        } else if number > 0.0 {
+        let sign = if number > 0.0 {
             1
-        } else {
+        } else if number < 0.0 {
             -1
+        } else {
+            0
+        };
+
+        if inverse {
+            -sign
+        } else {
+            sign
         }
     }
 
@@ -68,19 +72,82 @@ pub mod numeric {
     pub fn lerp(start: f64, end: f64, interp_factor: f64) -> f64 {
         start * (1.0 - interp_factor) + end * interp_factor
     }
+    /// The inverse of `lerp`: finds the interpolation factor at which `value` sits
+    /// between `start` and `end`, without clamping it to `[0, 1]`
+    /// ## Parameters
+    /// - `start`: The start value
+    /// - `end`: The end value
+    /// - `value`: The value to find the factor of
+    /// ## Returns
+    /// `(value - start) / (end - start)`, or `0` if `start == end`
+    pub fn inverse_lerp(start: f64, end: f64, value: f64) -> f64 {
+        if start == end {
+            return 0.0;
+        }
+        (value - start) / (end - start)
+    }
+    /// Like `inverse_lerp`, but clamped to `[0, 1]`
+    pub fn inverse_lerp_clamped(start: f64, end: f64, value: f64) -> f64 {
+        self::clamp(self::inverse_lerp(start, end, value), 0.0, 1.0)
+    }
     /// Limit a number to given bounds
     /// ## Parameters
     /// - `value`: Number to limit
     /// - `min`: Lower bound
     /// - `max`: Upper bound
+    ///
+    /// If `min > max`, the upper bound wins and `max` is always returned
     pub fn clamp(value: f64, min: f64, max: f64) -> f64 {
-        if value < max {
-            if value > min {
-                return value;
-            }
-            return min;
+        value.max(min).min(max)
+    }
+    /// Limit any partially ordered value to given bounds, like `clamp` does for floats
+    /// ## Parameters
+    /// - `value`: Value to limit
+    /// - `min`: Lower bound
+    /// - `max`: Upper bound
+    ///
+    /// If `min > max`, the upper bound wins and `max` is always returned
+    pub fn clamp_ord<T: PartialOrd>(value: T, min: T, max: T) -> T {
+        let value = if value < min { min } else { value };
+        if value > max { max } else { value }
+    }
+    /// Round a number to a fixed number of decimal places
+    /// ## Parameters
+    /// - `value`: Number to round
+    /// - `decimals`: Number of decimal places to keep
+    ///
+    /// Doubles only hold about 15 significant digits, so beyond `f64::DIGITS` decimals
+    /// (or whenever scaling `value` up would overflow) `value` is returned as is
+    pub fn round_to(value: f64, decimals: u32) -> f64 {
+        if decimals > f64::DIGITS {
+            return value;
         }
-        max
+
+        let factor = 10f64.powi(decimals as i32);
+        let scaled = value * factor;
+        if !scaled.is_finite() {
+            return value;
+        }
+        scaled.round() / factor
+    }
+    /// Hermite interpolation between `0` and `1` as `x` goes from `edge0` to `edge1`,
+    /// easing in and out of both edges
+    /// ## Parameters
+    /// - `edge0`: The value of `x` where the result is `0`
+    /// - `edge1`: The value of `x` where the result is `1`
+    /// - `x`: The value to interpolate
+    pub fn smoothstep(edge0: f64, edge1: f64, x: f64) -> f64 {
+        let t = self::clamp((x - edge0) / (edge1 - edge0), 0.0, 1.0);
+        t * t * (3.0 - 2.0 * t)
+    }
+    /// Like `smoothstep`, but with zero first and second derivatives at both edges
+    /// ## Parameters
+    /// - `edge0`: The value of `x` where the result is `0`
+    /// - `edge1`: The value of `x` where the result is `1`
+    /// - `x`: The value to interpolate
+    pub fn smootherstep(edge0: f64, edge1: f64, x: f64) -> f64 {
+        let t = self::clamp((x - edge0) / (edge1 - edge0), 0.0, 1.0);
+        t * t * t * (t * (6.0 * t - 15.0) + 10.0)
     }
     /// Uses linear interpolation in each range to
     /// remap a number from one range to another.
@@ -91,6 +158,44 @@ pub mod numeric {
             self::clamp((value - min0) / (max0 - min0), 0.0, 1.0),
         )
     }
+
+    /// Calculate the greatest common divisor of two numbers, using Euclid's algorithm.
+    /// Since every number divides 0, `gcd(0, n)` is `n`
+    /// ## Parameters
+    /// - `a`: The first number
+    /// - `b`: The second number
+    pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+
+    /// Calculate the least common multiple of two numbers; it's `0` if either of them is `0`
+    /// ## Parameters
+    /// - `a`: The first number
+    /// - `b`: The second number
+    pub fn lcm(a: u64, b: u64) -> u64 {
+        if a == 0 || b == 0 {
+            return 0;
+        }
+        a / self::gcd(a, b) * b
+    }
+
+    /// Rounds a number up to the nearest power of two; powers of two are returned as is,
+    /// and `0` gives `1`. Overflows if the result doesn't fit in a `usize`
+    /// ## Parameters
+    /// - `n`: The number to round
+    pub fn next_power_of_two(n: usize) -> usize {
+        n.next_power_of_two()
+    }
+
+    /// Checks whether a number is a power of two; `0` isn't
+    /// ## Parameters
+    /// - `n`: The number to check
+    pub fn is_power_of_two(n: usize) -> bool {
+        n.is_power_of_two()
+    }
 }
 
 pub mod angle {
@@ -117,6 +222,23 @@ pub mod angle {
     pub fn minimize(start: f64, end: f64) -> f64 {
         numeric::abs_mod(end - start + PI, TAU) - PI
     }
+    /// Interpolate between two angles along the shortest way around the circle
+    /// ## Parameters
+    /// - `start`: The start angle, in radians
+    /// - `end`: The end angle, in radians
+    /// - `t`: The interpolation factor
+    /// ## Returns
+    /// The interpolated angle, normalized to between -π and π
+    pub fn lerp_angle(start: f64, end: f64, t: f64) -> f64 {
+        normalize(start + minimize(start, end) * t)
+    }
+    /// Turn an angle into a unit vector pointing in its direction; `Vec2D::direction`
+    /// goes the other way
+    /// ## Parameters
+    /// - `radians`: The angle, in radians
+    pub fn to_vector(radians: f64) -> Vec2D {
+        Vec2D::from_polar(radians, None)
+    }
     /// Degrees to radians
     pub fn deg_to_rad(degrees: f64) -> f64 {
         degrees * PI / 180.0
@@ -129,9 +251,24 @@ pub mod angle {
     pub fn orientation_to_rotation(orientation: Orientation) -> f64 {
         -normalize((orientation as u8 as f64) * HALF_PI)
     }
+
+    /// Snap an angle to the closest of the four orientations; the inverse of
+    /// `orientation_to_rotation`
+    /// ## Parameters
+    /// - `radians`: The angle, in radians
+    pub fn nearest_orientation(radians: f64) -> Orientation {
+        [Orientation::Up, Orientation::Right, Orientation::Down, Orientation::Left]
+            .into_iter()
+            .min_by(|&a, &b| {
+                minimize(radians, orientation_to_rotation(a)).abs()
+                    .total_cmp(&minimize(radians, orientation_to_rotation(b)).abs())
+            })
+            .unwrap()
+    }
 }
 
 pub mod geometry {
+    use super::consts::PI;
     use super::Vec2D;
     use crate::typings::Orientation;
 
@@ -149,16 +286,16 @@ pub mod geometry {
         /// Translates this rectangle by a position.
         /// Mutates the original object, returns mutable reference to self for chaining.
         pub fn translate(&mut self, pos: Vec2D) -> &mut Self {
-            self.min = self.min + pos;
-            self.max = self.max + pos;
+            self.min += pos;
+            self.max += pos;
             self
         }
 
         /// Scale a rectangle by a factor.
         /// Mutates the original object, returns mutable reference to self for chaining.
         pub fn scale(&mut self, scale: f64) -> &mut Self {
-            self.min = self.min * scale;
-            self.max = self.max * scale;
+            self.min *= scale;
+            self.max *= scale;
             self
         }
 
@@ -190,6 +327,74 @@ pub mod geometry {
             }
             self
         }
+
+        /// Grows this rectangle by `margin` on every side, or shrinks it for a negative margin.
+        /// Shrinking past zero collapses an axis onto its center instead of inverting it.
+        /// Mutates the original object, returns mutable reference to self for chaining.
+        pub fn inflate(&mut self, margin: f64) -> &mut Self {
+            let center = (self.min + self.max) * 0.5;
+            let offset = Vec2D::new(margin, margin);
+            self.min = (self.min - offset).min_components(center);
+            self.max = (self.max + offset).max_components(center);
+            self
+        }
+
+        /// Check whether a point lies inside this rectangle; points on its edges count as inside
+        pub fn contains(&self, point: Vec2D) -> bool {
+            self.min.x <= point.x && point.x <= self.max.x && self.min.y <= point.y && point.y <= self.max.y
+        }
+
+        /// Check whether another rectangle lies entirely inside this one, edges included
+        pub fn contains_rect(&self, other: &Rectangle) -> bool {
+            self.contains(other.min) && self.contains(other.max)
+        }
+
+        pub fn width(&self) -> f64 {
+            self.max.x - self.min.x
+        }
+
+        pub fn height(&self) -> f64 {
+            self.max.y - self.min.y
+        }
+
+        pub fn area(&self) -> f64 {
+            self.width() * self.height()
+        }
+
+        pub fn perimeter(&self) -> f64 {
+            2.0 * (self.width() + self.height())
+        }
+
+        /// The region covered by both rectangles, or `None` if they don't overlap.
+        /// Rectangles that only share an edge or a corner don't overlap
+        pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+            let min = Vec2D::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+            let max = Vec2D::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+
+            if min.x < max.x && min.y < max.y {
+                Some(Rectangle { min, max })
+            } else {
+                None
+            }
+        }
+
+        /// The smallest rectangle containing both rectangles
+        pub fn union(&self, other: &Rectangle) -> Rectangle {
+            Rectangle {
+                min: Vec2D::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+                max: Vec2D::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+            }
+        }
+    }
+
+    impl Circle {
+        pub fn area(&self) -> f64 {
+            PI * self.radius * self.radius
+        }
+
+        pub fn circumference(&self) -> f64 {
+            2.0 * PI * self.radius
+        }
     }
 
     /// Calculate distance between two points
@@ -206,6 +411,14 @@ pub mod geometry {
     pub fn distance_squared(a: Vec2D, b: Vec2D) -> f64 {
         (b.x - a.x).powi(2) + (b.y - a.y).powi(2)
     }
+    /// Calculate the Chebyshev distance between two points, that is, the larger of
+    /// the distances along each axis
+    /// ## Parameters
+    /// - `a`: the first point
+    /// - `b`: the second point
+    pub fn chebyshev_distance(a: Vec2D, b: Vec2D) -> f64 {
+        (b.x - a.x).abs().max((b.y - a.y).abs())
+    }
     /// Calculate area of a triangle whose vertices are the three points passed in
     /// ## Parameters
     /// - `a`: the first vertex
@@ -214,10 +427,91 @@ pub mod geometry {
     pub fn signed_tri_area(a: Vec2D, b: Vec2D, c: Vec2D) -> f64 {
         (a.x - c.x) * (b.y - c.y) - (a.y - c.y) * (b.x - c.x)
     }
+    /// Find the point of a rectangle closest to a given point, which is the point itself
+    /// if it's inside the rectangle
+    /// ## Parameters
+    /// - `min`: the rectangle's minimum position
+    /// - `max`: the rectangle's maximum position
+    /// - `p`: the point
+    pub fn closest_point_on_rect(min: Vec2D, max: Vec2D, p: Vec2D) -> Vec2D {
+        Vec2D {
+            x: super::numeric::clamp(p.x, min.x, max.x),
+            y: super::numeric::clamp(p.y, min.y, max.y),
+        }
+    }
+
+    /// Calculate the point on a line segment that is closest to another point
+    /// ## Parameters
+    /// - `p`: the point
+    /// - `start`: the start of the segment
+    /// - `end`: the end of the segment
+    pub fn closest_point_on_segment(p: Vec2D, start: Vec2D, end: Vec2D) -> Vec2D {
+        let segment = end - start;
+        let length_squared = segment.squared_length();
+        if length_squared == 0.0 {
+            return start;
+        }
+
+        start + segment * super::numeric::clamp((p - start).dot(segment) / length_squared, 0.0, 1.0)
+    }
+    /// Calculate the centroid (center of mass) of a polygon, weighting each part by its area.
+    /// Polygons with no area fall back to the average of their vertices
+    /// ## Parameters
+    /// - `points`: the vertices of the polygon, in order
+    pub fn polygon_centroid(points: &[Vec2D]) -> Vec2D {
+        let len = points.len();
+        let mut doubled_area = 0.0;
+        let mut centroid = Vec2D::zero();
+
+        for i in 0..len {
+            let (a, b) = (points[i], points[(i + 1) % len]);
+            let cross = a.x * b.y - b.x * a.y;
+            doubled_area += cross;
+            centroid += (a + b) * cross;
+        }
+
+        if doubled_area.abs() < 1e-12 {
+            return points.iter().fold(Vec2D::zero(), |sum, &point| sum + point) / len as f64;
+        }
+        centroid / (3.0 * doubled_area)
+    }
+    /// Calculate the area of a polygon using the shoelace formula
+    /// ## Parameters
+    /// - `points`: the vertices of the polygon, in order (either winding works)
+    pub fn polygon_area(points: &[Vec2D]) -> f64 {
+        let len = points.len();
+        let doubled_area: f64 = (0..len)
+            .map(|i| {
+                let (a, b) = (points[i], points[(i + 1) % len]);
+                a.x * b.y - b.x * a.y
+            })
+            .sum();
+
+        doubled_area.abs() / 2.0
+    }
+    /// Check whether a point lies inside a polygon, using ray casting
+    /// ## Parameters
+    /// - `point`: the point to check
+    /// - `polygon`: the vertices of the polygon, in order
+    pub fn point_in_polygon(point: Vec2D, polygon: &[Vec2D]) -> bool {
+        let mut inside = false;
+        let len = polygon.len();
+        let mut j = len - 1;
+        for i in 0..len {
+            let (a, b) = (polygon[i], polygon[j]);
+            if (a.y > point.y) != (b.y > point.y)
+                && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+            {
+                inside = !inside;
+            }
+            j = i;
+        }
+        inside
+    }
 }
 
 pub mod intersections {
-    use super::numeric::{clamp, get_sign};
+    use super::numeric::get_sign;
     use super::CollisionResponse;
     use super::{IntersectionResponse, Vec2D};
     use crate::utils::math::geometry;
@@ -257,6 +551,11 @@ pub mod intersections {
         radius: f64,
     ) -> Option<CollisionResponse> {
         if min.x <= pos.x && pos.x <= max.x && min.y <= pos.y && pos.y <= max.y {
+            // a circle centered on the rectangle's axis still has to be pushed out somewhere
+            let push = |n: f64| match get_sign(n, false) {
+                0 => -1.0,
+                sign => f64::from(sign),
+            };
             let half_dimension: Vec2D = (max - min) * 0.5;
             let p = pos - (min + half_dimension);
             let xp = f64::abs(p.x) - half_dimension.x - radius;
@@ -264,18 +563,18 @@ pub mod intersections {
 
             if xp > yp {
                 return Some(CollisionResponse {
-                    dir: Vec2D::new(f64::from(get_sign(p.x, false)), 0.0),
+                    dir: Vec2D::new(push(p.x), 0.0),
                     pen: -xp,
                 });
             } else {
                 return Some(CollisionResponse {
-                    dir: Vec2D::new(0.0, f64::from(get_sign(p.y, false))),
+                    dir: Vec2D::new(0.0, push(p.y)),
                     pen: -yp,
                 });
             }
         }
 
-        let dir = Vec2D::new(clamp(pos.x, min.x, max.x), clamp(pos.y, min.y, max.y)) - pos;
+        let dir = geometry::closest_point_on_rect(min, max, pos) - pos;
         let dist_sqrd = Vec2D::squared_length(dir);
 
         if dist_sqrd < radius * radius {
@@ -321,8 +620,8 @@ pub mod intersections {
         line = line.normalize(None);
 
         let start_circle = start_point - circle_pos;
-        let proj_len = start_circle * line;
-        let sqrd_dist = start_circle * start_circle - circle_radius * circle_radius;
+        let proj_len = start_circle.dot(line);
+        let sqrd_dist = start_circle.dot(start_circle) - circle_radius * circle_radius;
 
         if sqrd_dist > 0.0 && proj_len > 0.0 {
             return None;
@@ -497,15 +796,15 @@ pub mod intersections {
 
     pub fn ray_line(origin: Vec2D, dir: Vec2D, start: Vec2D, end: Vec2D) -> Option<f64> {
         let segment = end - start;
-        let seg_perp = Vec2D::new(segment.y, -segment.x);
-        let perp_dot_dir = dir * seg_perp;
+        let seg_perp = segment.perpendicular_cw();
+        let perp_dot_dir = dir.dot(seg_perp);
 
         if perp_dot_dir.abs() <= 1e-7 {
             return None;
         }
         let d = start - origin;
-        let dist_along_ray = seg_perp * d / perp_dot_dir;
-        let dist_along_line = Vec2D::new(dir.y, -dir.x) * d / perp_dot_dir;
+        let dist_along_ray = seg_perp.dot(d) / perp_dot_dir;
+        let dist_along_line = dir.perpendicular_cw().dot(d) / perp_dot_dir;
 
         if dist_along_ray >= 0.0 && (0.0..=1.0).contains(&dist_along_line) {
             Some(dist_along_ray)
@@ -535,17 +834,102 @@ pub mod intersections {
             None
         }
     }
+
+    /// Calculate when a circle moving along `delta` first touches a rectangle, by casting its
+    /// center against the rectangle grown by the circle's radius (two slabs and four corner circles)
+    /// ## Parameters
+    /// - `start`: Center of the circle at the start of the movement
+    /// - `delta`: The movement of the circle
+    /// - `radius`: Radius of the circle
+    /// - `min`: Minimum position of the rectangle
+    /// - `max`: Maximum position of the rectangle
+    /// ## Returns
+    /// The fraction of `delta` travelled before the first contact, from `0` to `1`, or `None` if
+    /// the circle never touches the rectangle. A circle already overlapping it gives `None` too
+    pub fn swept_circle_rect(start: Vec2D, delta: Vec2D, radius: f64, min: Vec2D, max: Vec2D) -> Option<f64> {
+        if geometry::closest_point_on_rect(min, max, start).distance_squared_to(start) < radius * radius {
+            return None;
+        }
+
+        let grow_x = Vec2D::new(radius, 0.0);
+        let grow_y = Vec2D::new(0.0, radius);
+        let corners = [min, Vec2D::new(max.x, min.y), max, Vec2D::new(min.x, max.y)];
+
+        [
+            ray_rect(start, delta, min - grow_x, max + grow_x),
+            ray_rect(start, delta, min - grow_y, max + grow_y),
+        ]
+        .into_iter()
+        .chain(corners.into_iter().map(|corner| ray_circle(start, delta, corner, radius)))
+        .flatten()
+        .min_by(f64::total_cmp)
+    }
+
+    /// Calculate when a circle moving along `delta` first touches another, still circle
+    /// ## Parameters
+    /// - `start`: Center of the moving circle at the start of the movement
+    /// - `delta`: The movement of the circle
+    /// - `radius`: Radius of the moving circle
+    /// - `center`: Center of the other circle
+    /// - `other_radius`: Radius of the other circle
+    /// ## Returns
+    /// The fraction of `delta` travelled before the first contact, from `0` to `1`, or `None` if
+    /// the circles never touch or already overlap
+    pub fn swept_circles(start: Vec2D, delta: Vec2D, radius: f64, center: Vec2D, other_radius: f64) -> Option<f64> {
+        ray_circle(start, delta, center, radius + other_radius)
+    }
+
+    /// Where along `delta` (from `0` to `1`) a point starting outside a rectangle enters it
+    fn ray_rect(start: Vec2D, delta: Vec2D, min: Vec2D, max: Vec2D) -> Option<f64> {
+        let mut t_enter = f64::NEG_INFINITY;
+        let mut t_exit = f64::INFINITY;
+
+        for (s, d, low, high) in [(start.x, delta.x, min.x, max.x), (start.y, delta.y, min.y, max.y)] {
+            if d == 0.0 {
+                if s < low || s > high {
+                    return None;
+                }
+                continue;
+            }
+
+            let (t1, t2) = ((low - s) / d, (high - s) / d);
+            t_enter = t_enter.max(t1.min(t2));
+            t_exit = t_exit.min(t1.max(t2));
+        }
+
+        (t_enter <= t_exit && (0.0..=1.0).contains(&t_enter)).then_some(t_enter)
+    }
+
+    /// Where along `delta` (from `0` to `1`) a point starting outside a circle enters it
+    fn ray_circle(start: Vec2D, delta: Vec2D, center: Vec2D, radius: f64) -> Option<f64> {
+        let offset = start - center;
+        let a = delta.dot(delta);
+        let b = offset.dot(delta);
+        let c = offset.dot(offset) - radius * radius;
+
+        if a == 0.0 || c < 0.0 {
+            return None;
+        }
+
+        let discriminant = b * b - a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+
+        let t = (-b - discriminant.sqrt()) / a;
+        (0.0..=1.0).contains(&t).then_some(t)
+    }
 }
 
 pub mod collisions {
-    use super::numeric;
+    use super::geometry;
     use super::CollisionRecord;
     use super::Vec2D;
 
     pub mod distances {
         use super::CollisionRecord;
         use super::Vec2D;
-        use crate::utils::math::numeric;
+        use crate::utils::math::{geometry, numeric};
 
         /// Determines the distance between two circles.
         ///
@@ -576,6 +960,27 @@ pub mod collisions {
             }
         }
 
+        /// Determines the distance between a circle and a line segment.
+        ///
+        /// ## Parameters
+        /// - `start`: The start of the segment
+        /// - `end`: The end of the segment
+        /// - `position`: The center of the circle
+        /// - `radius`: The radius of the circle
+        ///
+        /// ## Returns
+        /// An object containing a boolean indicating whether the two shapes are colliding
+        /// and a number indicating the distance between them
+        pub fn circle_line(start: Vec2D, end: Vec2D, position: Vec2D, radius: f64) -> CollisionRecord {
+            let rad_squared = radius * radius;
+            let dist_squared = to_line(position, start, end);
+
+            CollisionRecord {
+                collided: dist_squared < rad_squared,
+                distance: dist_squared - rad_squared,
+            }
+        }
+
         /// Determines the distance between a circle and a rectangle.
         ///
         /// ## Parameters
@@ -616,16 +1021,17 @@ pub mod collisions {
         /// An object containing a boolean indicating whether the two circles are colliding
         /// and a number indicating the distance between them
         pub fn rects(min1: Vec2D, max1: Vec2D, min2: Vec2D, max2: Vec2D) -> CollisionRecord {
-            let dist_x = min1.x.max(max1.x.min(min2.x).min(max2.x))
-                - min1.x.min(max1.x.max(min2.x).max(max2.x));
-            let dist_y = min1.y.max(max1.y.min(min2.y).min(max2.y))
-                - min1.y.min(max1.y.max(min2.y).max(max2.y));
-            if dist_x < 0.0 || dist_y < 0.0 {
+            // size of the gap between the rectangles on each axis, negative if they overlap on it
+            let dist_x = min1.x.max(min2.x) - max1.x.min(max2.x);
+            let dist_y = min1.y.max(min2.y) - max1.y.min(max2.y);
+            if dist_x < 0.0 && dist_y < 0.0 {
                 return CollisionRecord {
                     collided: true,
                     distance: 0.0,
                 };
             }
+            let dist_x = dist_x.max(0.0);
+            let dist_y = dist_y.max(0.0);
             let dist_sq = dist_x * dist_x + dist_y * dist_y;
             CollisionRecord {
                 collided: false,
@@ -633,14 +1039,87 @@ pub mod collisions {
             }
         }
 
+        /// Determines the distance between a circle and a polygon.
+        ///
+        /// ## Parameters
+        /// - `points`: The vertices of the polygon, in order
+        /// - `position`: The center of the circle
+        /// - `radius`: The radius of the circle
+        ///
+        /// ## Returns
+        /// An object containing a boolean indicating whether the two shapes are colliding
+        /// and a number indicating the distance between them
+        pub fn circle_polygon(points: &[Vec2D], position: Vec2D, radius: f64) -> CollisionRecord {
+            if geometry::point_in_polygon(position, points) {
+                return CollisionRecord {
+                    collided: true,
+                    distance: -radius * radius,
+                };
+            }
+
+            let len = points.len();
+            let dist_squared = (0..len)
+                .map(|i| to_line(position, points[i], points[(i + 1) % len]))
+                .fold(f64::INFINITY, f64::min);
+            let rad_squared = radius * radius;
+
+            CollisionRecord {
+                collided: dist_squared < rad_squared,
+                distance: dist_squared - rad_squared,
+            }
+        }
+
+        /// Determines the distance between two convex polygons.
+        ///
+        /// ## Parameters
+        /// - `points_a`: The vertices of the first polygon, in order
+        /// - `points_b`: The vertices of the second polygon, in order
+        ///
+        /// ## Returns
+        /// An object containing a boolean indicating whether the two polygons are colliding
+        /// and a number indicating the distance between them
+        pub fn polygons(points_a: &[Vec2D], points_b: &[Vec2D]) -> CollisionRecord {
+            if super::check_polygons(points_a, points_b) {
+                return CollisionRecord {
+                    collided: true,
+                    distance: 0.0,
+                };
+            }
+
+            fn min_dist_squared(points: &[Vec2D], polygon: &[Vec2D]) -> f64 {
+                let len = polygon.len();
+                points
+                    .iter()
+                    .flat_map(|p| (0..len).map(move |i| to_line(*p, polygon[i], polygon[(i + 1) % len])))
+                    .fold(f64::INFINITY, f64::min)
+            }
+
+            CollisionRecord {
+                collided: false,
+                distance: min_dist_squared(points_a, points_b).min(min_dist_squared(points_b, points_a)),
+            }
+        }
+
         pub fn to_line(p: Vec2D, start: Vec2D, end: Vec2D) -> f64 {
             let segment = end - start;
             ((start
                 + segment
-                    * numeric::clamp(((p - start) * segment) / (segment * segment), 0.0, 1.0))
+                    * numeric::clamp((p - start).dot(segment) / segment.dot(segment), 0.0, 1.0))
                 - p)
                 .squared_length()
         }
+
+        /// Determines the squared distance between two line segments, which is 0 if they cross
+        pub fn segments(start_a: Vec2D, end_a: Vec2D, start_b: Vec2D, end_b: Vec2D) -> f64 {
+            if crate::utils::math::intersections::line_line(start_a, end_a, start_b, end_b).is_some() {
+                return 0.0;
+            }
+
+            to_line(start_a, start_b, end_b)
+                .min(to_line(end_a, start_b, end_b))
+                .min(to_line(start_b, start_a, end_a))
+                .min(to_line(end_b, start_a, end_a))
+        }
     }
 
     /// Check for collision between two circles.
@@ -674,19 +1153,27 @@ pub mod collisions {
     /// ## Returns
     /// Returns `true` if the shapes collide, `false` otherwise.
     pub fn check_rect_circle(min: Vec2D, max: Vec2D, pos: Vec2D, rad: f64) -> bool {
-        let cpt = Vec2D {
-            x: numeric::clamp(pos.x, min.x, max.x),
-            y: numeric::clamp(pos.y, min.y, max.y),
-        };
-
-        let distance_x = pos.x - cpt.x;
-        let distance_y = pos.y - cpt.y;
-        let distance_squared = distance_x * distance_x + distance_y * distance_y;
+        let distance_squared = pos.distance_squared_to(geometry::closest_point_on_rect(min, max, pos));
 
         (distance_squared < rad * rad)
             || (pos.x >= min.x && pos.x <= max.x && pos.y >= min.y && pos.y <= max.y)
     }
 
+    /// Check for collision between a circle and a polygon, which doesn't need to be convex.
+    ///
+    /// ## Parameters
+    /// - `points`: The vertices of the polygon, in order
+    /// - `center`: The center of the circle
+    /// - `radius`: The radius of the circle
+    ///
+    /// ## Returns
+    /// Returns `true` if the shapes collide, `false` otherwise.
+    pub fn check_circle_polygon(points: &[Vec2D], center: Vec2D, radius: f64) -> bool {
+        let len = points.len();
+        geometry::point_in_polygon(center, points)
+            || (0..len).any(|i| distances::to_line(center, points[i], points[(i + 1) % len]) < radius * radius)
+    }
+
     /// Check for collision between two rectangles.
     ///
     /// ## Parameters
@@ -700,6 +1187,77 @@ pub mod collisions {
     pub fn check_rects(min_a: Vec2D, max_a: Vec2D, min_b: Vec2D, max_b: Vec2D) -> bool {
         min_b.x < max_a.x && min_b.y < max_a.y && min_a.x < max_b.x && min_a.y < max_b.y
     }
+
+    /// Finds every pair of overlapping axis-aligned bounding boxes by sorting them along
+    /// the x axis and only comparing boxes whose x intervals overlap. This works well
+    /// for mostly-static scenes, where boxes are spread out along the axis.
+    ///
+    /// ## Parameters
+    /// - `boxes`: The `(min, max)` corners of each bounding box
+    ///
+    /// ## Returns
+    /// The index pairs of the overlapping boxes, with the smaller index first,
+    /// sorted in ascending order
+    pub fn sweep_and_prune(boxes: &[(Vec2D, Vec2D)]) -> Vec<(usize, usize)> {
+        let mut order: Vec<usize> = (0..boxes.len()).collect();
+        order.sort_by(|&a, &b| boxes[a].0.x.total_cmp(&boxes[b].0.x));
+
+        let mut pairs: Vec<(usize, usize)> = vec![];
+        for (i, &a) in order.iter().enumerate() {
+            let (min_a, max_a) = boxes[a];
+            for &b in &order[i + 1..] {
+                let (min_b, max_b) = boxes[b];
+                // every remaining box starts after this one ends
+                if min_b.x >= max_a.x {
+                    break;
+                }
+                if check_rects(min_a, max_a, min_b, max_b) {
+                    pairs.push((a.min(b), a.max(b)));
+                }
+            }
+        }
+
+        pairs.sort_unstable();
+        pairs
+    }
+
+    /// Check for collision between two convex polygons using the Separating Axis Theorem.
+    ///
+    /// Both point sets are projected onto the normal of every edge; if any of those axes
+    /// shows a gap between the two projections, the polygons don't collide. This assumes
+    /// convex polygons; concave input may report false positives.
+    ///
+    /// ## Parameters
+    /// - `points_a`: The vertices of the first polygon, in order
+    /// - `points_b`: The vertices of the second polygon, in order
+    ///
+    /// ## Returns
+    /// Returns `true` if the polygons collide, `false` otherwise.
+    pub fn check_polygons(points_a: &[Vec2D], points_b: &[Vec2D]) -> bool {
+        fn project(points: &[Vec2D], axis: Vec2D) -> (f64, f64) {
+            points.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), point| {
+                let proj = point.dot(axis);
+                (min.min(proj), max.max(proj))
+            })
+        }
+
+        for points in [points_a, points_b] {
+            let len = points.len();
+            for i in 0..len {
+                let edge = points[(i + 1) % len] - points[i];
+                let axis = edge.perpendicular();
+
+                let (min_a, max_a) = project(points_a, axis);
+                let (min_b, max_b) = project(points_b, axis);
+
+                if max_a <= min_b || max_b <= min_a {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
 }
 
 pub mod ease {
@@ -71,4 +71,16 @@ pub fn remove_from<T: PartialEq>(list: &mut Vec<T>, value: T) {
     }
 }
 
+/// Returns how many bits are needed to represent `count` distinct values, such as the
+/// variants of an enum. A `count` of 0 or 1 needs no bits at all, and gives 0.
+/// ## Parameters
+/// - `count`: The number of distinct values
+pub const fn bits_for(count: usize) -> usize {
+    if count <= 1 {
+        0
+    } else {
+        (usize::BITS - (count - 1).leading_zeros()) as usize
+    }
+}
+
 pub const CARDINAL_DIRECTIONS: [f64; 4] = [0.0, HALF_PI, PI, 1.5 * PI];
@@ -0,0 +1,327 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::f64::consts::SQRT_2;
+
+use super::math::{consts::TAU, geometry, intersections};
+use super::hitbox::{hitbox_intersects_line, hitbox_outlines, Collidable, Hitbox, RectangleHitbox};
+use super::vectors::Vec2D;
+
+fn overlaps_rect(hitbox: &Hitbox, min: Vec2D, max: Vec2D) -> bool {
+    match hitbox {
+        Hitbox::Circle(hitbox) => hitbox.overlaps_rect(min, max),
+        Hitbox::Rect(hitbox) => hitbox.overlaps_rect(min, max),
+        Hitbox::Group(hitbox) => hitbox.overlaps_rect(min, max),
+        Hitbox::Polygon(hitbox) => hitbox.overlaps_rect(min, max),
+        Hitbox::Line(hitbox) => hitbox.overlaps_rect(min, max),
+        Hitbox::Capsule(hitbox) => hitbox.overlaps_rect(min, max),
+    }
+}
+
+fn bounds_of(hitbox: &Hitbox) -> (Vec2D, Vec2D) {
+    let rect = match hitbox {
+        Hitbox::Circle(hitbox) => hitbox.as_rectangle(),
+        Hitbox::Rect(hitbox) => hitbox.as_rectangle(),
+        Hitbox::Group(hitbox) => hitbox.as_rectangle(),
+        Hitbox::Polygon(hitbox) => hitbox.as_rectangle(),
+        Hitbox::Line(hitbox) => hitbox.as_rectangle(),
+        Hitbox::Capsule(hitbox) => hitbox.as_rectangle(),
+    };
+    let [min, _, max, _] = rect.corners();
+    (min, max)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Influence {
+    pub vector: Vec2D,
+    /// Sum of the lengths of every vector added to this cell, regardless of direction
+    pub magnitude: f64,
+}
+
+/// Accumulates directional influence on a grid of square cells, e.g. to build flow fields for AI
+#[derive(Debug, Clone)]
+pub struct InfluenceMap {
+    cell_size: f64,
+    cells: HashMap<(i64, i64), Influence>,
+}
+
+impl InfluenceMap {
+    pub fn new(cell_size: f64) -> InfluenceMap {
+        assert!(cell_size > 0.0, "InfluenceMap cell size must be positive");
+        InfluenceMap {
+            cell_size,
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn cell_size(&self) -> f64 {
+        self.cell_size
+    }
+
+    pub fn get(&self, cell: (i64, i64)) -> Option<&Influence> {
+        self.cells.get(&cell)
+    }
+
+    /// Adds `vector` to the cell `pos` lies in
+    pub fn add(&mut self, pos: Vec2D, vector: Vec2D) {
+        let influence = self
+            .cells
+            .entry(pos.grid_cell(self.cell_size))
+            .or_insert(Influence {
+                vector: Vec2D::zero(),
+                magnitude: 0.0,
+            });
+
+        influence.vector += vector;
+        influence.magnitude += vector.length();
+    }
+
+    /// Bilinearly interpolates the vectors of the four cells whose centers surround `pos`;
+    /// empty cells count as the zero vector
+    pub fn sample(&self, pos: Vec2D) -> Vec2D {
+        let grid = pos / self.cell_size - Vec2D::new(0.5, 0.5);
+        let base = grid.floor();
+        let t = grid - base;
+        let (x, y) = (base.x as i64, base.y as i64);
+
+        let at = |cell: (i64, i64)| {
+            self.cells
+                .get(&cell)
+                .map_or(Vec2D::zero(), |influence| influence.vector)
+        };
+
+        let top = at((x, y)).lerp(at((x + 1, y)), t.x);
+        let bottom = at((x, y + 1)).lerp(at((x + 1, y + 1)), t.x);
+        top.lerp(bottom, t.y)
+    }
+
+    /// Scales every cell by `factor`, dropping the ones whose influence becomes negligible
+    pub fn decay(&mut self, factor: f64) {
+        self.cells.retain(|_, influence| {
+            influence.vector *= factor;
+            influence.magnitude *= factor;
+            influence.magnitude > 1e-6
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+}
+
+/// Splits `bounds` into square cells with a side length of `cell` and marks the ones
+/// overlapped by any of the `obstacles` as blocked (`true`). The grid is indexed as
+/// `grid[x][y]`, with cell `(0, 0)` at the top-left corner of `bounds`; cells on the
+/// right and bottom edges are cut short if `bounds` isn't a multiple of `cell`
+pub fn build_nav_grid(obstacles: &[Hitbox], bounds: &RectangleHitbox, cell: f64) -> Vec<Vec<bool>> {
+    assert!(cell > 0.0, "Nav grid cell size must be positive");
+
+    let [min, _, max, _] = bounds.corners();
+    let size = max - min;
+    let columns = (size.x / cell).ceil() as usize;
+    let rows = (size.y / cell).ceil() as usize;
+
+    (0..columns)
+        .map(|x| {
+            (0..rows)
+                .map(|y| {
+                    let cell_min = min + Vec2D::new(x as f64, y as f64) * cell;
+                    let cell_max = (cell_min + Vec2D::new(cell, cell)).min_components(max);
+
+                    obstacles
+                        .iter()
+                        .any(|obstacle| overlaps_rect(obstacle, cell_min, cell_max))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[derive(PartialEq)]
+struct OpenNode {
+    cost: f64,
+    cell: (i32, i32),
+}
+
+impl Eq for OpenNode {}
+
+impl Ord for OpenNode {
+    // reversed, so that the `BinaryHeap` pops the cheapest node first
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn is_open(grid: &[Vec<bool>], (x, y): (i32, i32)) -> bool {
+    x >= 0
+        && y >= 0
+        && grid
+            .get(x as usize)
+            .and_then(|column| column.get(y as usize))
+            .is_some_and(|&blocked| !blocked)
+}
+
+/// Finds the shortest path between two cells of a grid built by `build_nav_grid`, moving
+/// in 8 directions; diagonal moves cost `√2` and can't cut through the corner of a blocked
+/// cell. The returned path includes both `start` and `goal`, and is `None` if either of
+/// them is blocked or out of the grid, or if `goal` can't be reached
+pub fn astar(grid: &[Vec<bool>], start: (i32, i32), goal: (i32, i32)) -> Option<Vec<(i32, i32)>> {
+    if !is_open(grid, start) || !is_open(grid, goal) {
+        return None;
+    }
+
+    let heuristic = |(x, y): (i32, i32)| {
+        geometry::chebyshev_distance(
+            Vec2D::new(x as f64, y as f64),
+            Vec2D::new(goal.0 as f64, goal.1 as f64),
+        )
+    };
+
+    let mut open = BinaryHeap::from([OpenNode { cost: heuristic(start), cell: start }]);
+    let mut came_from: HashMap<(i32, i32), (i32, i32)> = HashMap::new();
+    let mut best: HashMap<(i32, i32), f64> = HashMap::from([(start, 0.0)]);
+
+    while let Some(OpenNode { cost, cell }) = open.pop() {
+        if cell == goal {
+            let mut path = vec![goal];
+            let mut current = goal;
+            while let Some(&previous) = came_from.get(&current) {
+                path.push(previous);
+                current = previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        let travelled = best[&cell];
+        // stale entry, a cheaper way to this cell was already expanded
+        if cost > travelled + heuristic(cell) {
+            continue;
+        }
+
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+
+                let next = (cell.0 + dx, cell.1 + dy);
+                if !is_open(grid, next) {
+                    continue;
+                }
+
+                let diagonal = dx != 0 && dy != 0;
+                if diagonal && !(is_open(grid, (cell.0 + dx, cell.1)) && is_open(grid, (cell.0, cell.1 + dy))) {
+                    continue;
+                }
+
+                let next_travelled = travelled + if diagonal { SQRT_2 } else { 1.0 };
+                if best.get(&next).is_some_and(|&known| known <= next_travelled) {
+                    continue;
+                }
+
+                best.insert(next, next_travelled);
+                came_from.insert(next, cell);
+                open.push(OpenNode { cost: next_travelled + heuristic(next), cell: next });
+            }
+        }
+    }
+
+    None
+}
+
+/// Removes the waypoints of `path` that can be skipped by going straight from an earlier
+/// waypoint to a later one, according to `is_clear` (typically a line of sight test).
+/// The first and last waypoints are always kept
+pub fn smooth_path(path: &[Vec2D], is_clear: impl Fn(Vec2D, Vec2D) -> bool) -> Vec<Vec2D> {
+    let (Some(&first), Some(&last)) = (path.first(), path.last()) else {
+        return vec![];
+    };
+    if path.len() <= 2 {
+        return path.to_vec();
+    }
+
+    let mut smoothed = vec![first];
+    let mut anchor = first;
+    for window in path.windows(2) {
+        let (waypoint, next) = (window[0], window[1]);
+        if waypoint != anchor && !is_clear(anchor, next) {
+            smoothed.push(waypoint);
+            anchor = waypoint;
+        }
+    }
+    smoothed.push(last);
+
+    smoothed
+}
+
+/// Checks whether the segment from `from` to `to` doesn't cross any of the `obstacles`.
+/// Segments that merely touch an obstacle (like a tangent to a circle) count as blocked
+pub fn line_of_sight(from: Vec2D, to: Vec2D, obstacles: &[Hitbox]) -> bool {
+    let line_min = from.min_components(to);
+    let line_max = from.max_components(to);
+
+    !obstacles.iter().any(|obstacle| {
+        let (min, max) = bounds_of(obstacle);
+        // inclusive, unlike check_rects, so that axis-aligned segments touching an edge aren't skipped
+        let bounds_overlap = line_min.x <= max.x
+            && min.x <= line_max.x
+            && line_min.y <= max.y
+            && min.y <= line_max.y;
+
+        bounds_overlap && hitbox_intersects_line(obstacle, from, to).is_some()
+    })
+}
+
+/// Number of segments circles are approximated with when computing visibility
+const VISIBILITY_CIRCLE_SEGMENTS: usize = 24;
+/// Number of evenly spaced rays cast regardless of obstacles, so that the edge of the
+/// visible region follows `max_range` where nothing is in the way
+const VISIBILITY_BASE_RAYS: usize = 64;
+/// Angle rays are offset by on each side of an obstacle corner, to see past it
+const VISIBILITY_CORNER_OFFSET: f64 = 1e-4;
+
+/// Computes the region visible from `origin`, as a polygon with its vertices sorted by angle.
+/// Rays are cast towards every obstacle corner (and slightly to each side of it) and stop at
+/// the first obstacle they hit, or after `max_range` units
+pub fn visibility_polygon(origin: Vec2D, obstacles: &[Hitbox], max_range: f64) -> Vec<Vec2D> {
+    let outlines: Vec<Vec<Vec2D>> = obstacles
+        .iter()
+        .flat_map(|obstacle| hitbox_outlines(obstacle, VISIBILITY_CIRCLE_SEGMENTS))
+        .collect();
+
+    let mut angles: Vec<f64> = (0..VISIBILITY_BASE_RAYS)
+        .map(|i| TAU * i as f64 / VISIBILITY_BASE_RAYS as f64)
+        .collect();
+    for corner in outlines.iter().flatten() {
+        let angle = (*corner - origin).direction();
+        angles.extend([
+            angle - VISIBILITY_CORNER_OFFSET,
+            angle,
+            angle + VISIBILITY_CORNER_OFFSET,
+        ]);
+    }
+
+    // normalize to [0, τ) so that the polygon winds around the origin once
+    let mut angles: Vec<f64> = angles.into_iter().map(|angle| angle.rem_euclid(TAU)).collect();
+    angles.sort_by(f64::total_cmp);
+    angles.dedup();
+
+    angles
+        .into_iter()
+        .map(|angle| {
+            let dir = Vec2D::from_polar(angle, None);
+            let distance = outlines
+                .iter()
+                .filter_map(|outline| intersections::ray_polygon(origin, dir, outline))
+                .fold(max_range, f64::min);
+
+            origin + dir * distance
+        })
+        .collect()
+}
@@ -0,0 +1,101 @@
+use super::hitbox::{hitbox_contact_normal, hitbox_sweep, Hitbox};
+use super::vectors::Vec2D;
+
+/// How many times a movement is allowed to be redirected along a wall before giving up
+const MAX_SLIDE_ITERATIONS: usize = 3;
+
+/// Computes the velocities of two colliding circles after they bounce off each other,
+/// exchanging momentum along the line between their centers
+/// ## Parameters
+/// - `v_a`, `v_b`: The velocities before the collision
+/// - `pos_a`, `pos_b`: The centers of the circles
+/// - `mass_a`, `mass_b`: The masses of the circles
+/// - `restitution`: How bouncy the collision is, from `0` (the circles stick together
+///   along the normal) to `1` (perfectly elastic)
+/// ## Returns
+/// The velocities after the collision; circles already moving apart are left as is
+pub fn resolve_velocities(
+    v_a: Vec2D,
+    v_b: Vec2D,
+    pos_a: Vec2D,
+    pos_b: Vec2D,
+    mass_a: f64,
+    mass_b: f64,
+    restitution: f64,
+) -> (Vec2D, Vec2D) {
+    let normal = (pos_b - pos_a).normalize(None);
+    let closing_speed = (v_a - v_b).dot(normal);
+
+    if closing_speed <= 0.0 {
+        return (v_a, v_b);
+    }
+
+    let impulse = (1.0 + restitution) * closing_speed / (1.0 / mass_a + 1.0 / mass_b);
+    (
+        v_a - normal * (impulse / mass_a),
+        v_b + normal * (impulse / mass_b),
+    )
+}
+
+/// Slows a velocity down by a drag factor, as returned by `drag_const`, for one tick.
+/// The factor is clamped to `[0, 1]` so that drag can neither speed things up nor
+/// reverse their direction
+pub fn apply_drag(velocity: Vec2D, drag: f32) -> Vec2D {
+    velocity * (drag as f64).clamp(0.0, 1.0)
+}
+
+/// Same as `apply_drag`, for a speed instead of a velocity
+pub fn apply_drag_scalar(speed: f64, drag: f32) -> f64 {
+    speed * (drag as f64).clamp(0.0, 1.0)
+}
+
+/// Moves `pos` by `speed * dt` units towards `direction`. The direction is normalized first,
+/// so diagonal input isn't faster than cardinal input; no input (the zero vector) means no movement
+pub fn integrate_movement(pos: Vec2D, direction: Vec2D, speed: f64, dt: f64) -> Vec2D {
+    pos + direction.with_length(speed * dt)
+}
+
+/// Moves a circle by `delta`, sliding along any obstacle it would run into instead of
+/// stopping dead: the circle moves up to the wall, the part of the remaining movement going
+/// into the wall is removed, and the rest is retried. A circle wedged into a corner stops
+/// against both walls
+pub fn move_with_collision(pos: Vec2D, radius: f64, delta: Vec2D, obstacles: &[Hitbox]) -> Vec2D {
+    let mut pos = pos;
+    let mut delta = delta;
+
+    for _ in 0..MAX_SLIDE_ITERATIONS {
+        let target = pos + delta;
+        // obstacles we're already moving away from (e.g. after spawning inside one) don't block us
+        let wall = obstacles
+            .iter()
+            .filter_map(|obstacle| hitbox_contact_normal(obstacle, target, radius))
+            .find(|normal| delta.dot(*normal) < 0.0);
+
+        let Some(normal) = wall else {
+            return target;
+        };
+
+        // move up to the first contact, then slide with whatever movement is left
+        let contact = obstacles
+            .iter()
+            .filter_map(|obstacle| hitbox_sweep(obstacle, pos, radius, delta))
+            .min_by(f64::total_cmp)
+            .unwrap_or(0.0);
+        pos += delta * contact;
+        delta *= 1.0 - contact;
+        delta -= normal * delta.dot(normal);
+    }
+
+    pos
+}
+
+/// Moves a circle by `delta`, stopping it where it first touches an obstacle along the way.
+/// Unlike checking only the destination, this can't skip over walls thinner than the movement
+pub fn swept_move(start: Vec2D, radius: f64, delta: Vec2D, obstacles: &[Hitbox]) -> Vec2D {
+    let contact = obstacles
+        .iter()
+        .filter_map(|obstacle| hitbox_sweep(obstacle, start, radius, delta))
+        .min_by(f64::total_cmp);
+
+    start + delta * contact.unwrap_or(1.0)
+}
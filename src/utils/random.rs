@@ -3,7 +3,12 @@ use rand::distributions::uniform::SampleUniform;
 use std::f64::consts::PI;
 use crate::utils::vectors::Vec2D;
 
+/// The RNG to pass around for gameplay randomness that should be reproducible, e.g.
+/// `GameRng::seed_from_u64(seed)`; everything else can keep using `thread_rng`
+pub type GameRng = StdRng;
+
 /// Returns a randomly selected item from the given slice of items based on the provided weights.
+/// Items with a weight of zero (or less) are never selected.
 ///
 /// # Arguments
 /// * `items` - A reference to a slice of items of type T.
@@ -12,17 +17,28 @@ use crate::utils::vectors::Vec2D;
 /// # Returns
 /// A reference to the selected item from the slice.
 ///
+/// # Panics
+/// If no weight is positive.
+///
 pub fn weighted_random<'a, T>(items: &'a [T], weights: &[f64]) -> &'a T {
-    let mut i: usize = 0;
-    let mut pick: f64 = rand::thread_rng().gen::<f64>() * weights.iter().sum::<f64>();
-
-    loop {
-        pick -= weights[i];
-        i += 1;
-        if pick <= 0.0 {
-            return &items[i-1];
+    let mut pick: f64 = rand::thread_rng().gen::<f64>() * weights.iter().filter(|w| **w > 0.0).sum::<f64>();
+    let mut last: Option<usize> = None;
+
+    for (i, weight) in weights.iter().enumerate() {
+        if *weight <= 0.0 {
+            continue;
+        }
+
+        pick -= weight;
+        last = Some(i);
+        if pick < 0.0 {
+            return &items[i];
         }
     }
+
+    // the weights' sum can be slightly off due to floating-point error, leaving
+    // some of the pick over; that leftover belongs to the last eligible item
+    &items[last.expect("weighted_random needs at least one positive weight")]
 }
 
 pub fn random_float(min: f64, max: f64) -> f64 {
@@ -75,3 +91,47 @@ pub fn random_point_in_circle(pos: Vec2D, min_radius: Option<f64>, max_radius: f
 pub fn random_item<T>(items: &[T]) -> &T {
     &items[random_int(0, items.len() as i64) as usize]
 }
+
+/// Shuffles a slice in place so that the same `seed` (such as a match id) always gives
+/// the same order, for reproducible map generation. The order can change when `rand`
+/// is upgraded, since `GameRng` doesn't promise a stable output across versions
+pub fn seeded_shuffle<T>(items: &mut [T], seed: u64) {
+    items.shuffle(&mut GameRng::seed_from_u64(seed));
+}
+
+/// Draws `k` distinct indices into `weights`, each draw picking an index with a probability
+/// proportional to its weight among the ones not drawn yet. Like `weighted_random`, indices
+/// with a weight of zero (or less) are never drawn, so asking for more indices than there
+/// are positive weights returns all of those, in the order they were drawn
+pub fn weighted_sample(weights: &[f64], k: usize, rng: &mut GameRng) -> Vec<usize> {
+    let mut remaining: Vec<f64> = weights.iter().map(|weight| weight.max(0.0)).collect();
+    let mut drawn = Vec::with_capacity(k.min(weights.len()));
+
+    while drawn.len() < k {
+        let total: f64 = remaining.iter().sum();
+        if total <= 0.0 {
+            break;
+        }
+
+        let mut pick = rng.gen::<f64>() * total;
+        let mut last: Option<usize> = None;
+        for (i, weight) in remaining.iter().enumerate() {
+            if *weight <= 0.0 {
+                continue;
+            }
+
+            pick -= weight;
+            last = Some(i);
+            if pick < 0.0 {
+                break;
+            }
+        }
+
+        // same as in `weighted_random`, leftover from rounding errors goes to the last index
+        let index = last.expect("a positive total means there's a positive weight");
+        remaining[index] = 0.0;
+        drawn.push(index);
+    }
+
+    drawn
+}
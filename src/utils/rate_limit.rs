@@ -0,0 +1,56 @@
+use std::time::Instant;
+
+/// Token bucket rate limiter: the bucket holds up to `capacity` tokens and gains
+/// `refill_per_sec` of them every second. Actions cost tokens, and are refused when
+/// the bucket doesn't have enough left, which allows short bursts while capping the
+/// average rate. Used for player actions like emotes and map pings
+#[derive(Debug, Clone, Copy)]
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_sec: f64,
+    tokens: f64,
+    /// When the bucket was last refilled; `None` until its first use
+    last_refill: Option<Instant>,
+}
+
+impl TokenBucket {
+    /// Creates a full bucket
+    pub fn new(capacity: f64, refill_per_sec: f64) -> TokenBucket {
+        assert!(capacity > 0.0, "Bucket capacity must be positive, got {}", capacity);
+        assert!(refill_per_sec >= 0.0, "Refill rate cannot be negative, got {}", refill_per_sec);
+
+        TokenBucket {
+            capacity,
+            refill_per_sec,
+            tokens: capacity,
+            last_refill: None,
+        }
+    }
+
+    /// Takes `tokens` tokens from the bucket if it has enough of them, after refilling
+    /// it for the time elapsed since the last call. Returns whether the action is allowed;
+    /// refused actions don't take anything
+    pub fn try_take(&mut self, now: Instant, tokens: f64) -> bool {
+        self.refill(now);
+
+        if self.tokens < tokens {
+            return false;
+        }
+        self.tokens -= tokens;
+        true
+    }
+
+    /// How many tokens the bucket had after its last refill
+    pub fn tokens(&self) -> f64 {
+        self.tokens
+    }
+
+    fn refill(&mut self, now: Instant) {
+        if let Some(last_refill) = self.last_refill {
+            // `saturating_duration_since` gives 0 if `now` is somehow earlier
+            let elapsed = now.saturating_duration_since(last_refill).as_secs_f64();
+            self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        }
+        self.last_refill = Some(self.last_refill.map_or(now, |last_refill| last_refill.max(now)));
+    }
+}
@@ -0,0 +1,71 @@
+use super::vectors::Vec2D;
+
+/// Exponential moving average, for smoothing out noisy inputs like aim or network jitter.
+/// Each update moves the average `alpha` of the way towards the new sample, so higher
+/// values of `alpha` react faster and smooth less
+#[derive(Debug, Clone, Copy)]
+pub struct Ema {
+    alpha: f64,
+    value: Option<f64>,
+}
+
+impl Ema {
+    pub fn new(alpha: f64) -> Ema {
+        assert!(alpha > 0.0 && alpha <= 1.0, "Smoothing factor must be in (0, 1], got {}", alpha);
+        Ema { alpha, value: None }
+    }
+
+    /// Feeds a new sample into the average and returns the updated average. The
+    /// first sample is taken as is
+    pub fn update(&mut self, sample: f64) -> f64 {
+        let value = match self.value {
+            Some(value) => value + (sample - value) * self.alpha,
+            None => sample,
+        };
+        self.value = Some(value);
+        value
+    }
+
+    /// The current average, or `None` if no sample has been fed yet
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+
+    pub fn reset(&mut self) {
+        self.value = None;
+    }
+}
+
+/// Same as `Ema`, for smoothing positions and directions
+#[derive(Debug, Clone, Copy)]
+pub struct Vec2DEma {
+    alpha: f64,
+    value: Option<Vec2D>,
+}
+
+impl Vec2DEma {
+    pub fn new(alpha: f64) -> Vec2DEma {
+        assert!(alpha > 0.0 && alpha <= 1.0, "Smoothing factor must be in (0, 1], got {}", alpha);
+        Vec2DEma { alpha, value: None }
+    }
+
+    /// Feeds a new sample into the average and returns the updated average. The
+    /// first sample is taken as is
+    pub fn update(&mut self, sample: Vec2D) -> Vec2D {
+        let value = match self.value {
+            Some(value) => value.lerp(sample, self.alpha),
+            None => sample,
+        };
+        self.value = Some(value);
+        value
+    }
+
+    /// The current average, or `None` if no sample has been fed yet
+    pub fn value(&self) -> Option<Vec2D> {
+        self.value
+    }
+
+    pub fn reset(&mut self) {
+        self.value = None;
+    }
+}
@@ -1,18 +1,29 @@
 use std::f64::consts::PI;
 
-use crate::constants::GAME_CONSTANTS;
-
-use super::bitstream::{BitStream, Endianness, Stream};
+use crate::constants::{ObjectCategory, ZIndexes, GAME_CONSTANTS};
+use crate::typings::{CustomTeamMessage, CustomTeamPlayerInfo, GameRejectType, GameResponse, JoinPacket};
+use std::borrow::Cow;
+use strum::EnumCount;
+
+use super::bitstream::{BitStream, Endianness, Stream, StreamError};
+use super::decimal::FixedPoint;
+use super::misc::bits_for;
 use super::vectors::Vec2D;
 
 
-// FIXME
-// pub const OBJECT_CATEGORY_BITS: usize = (ObjectCategory::COUNT as f64).log2().ceil() as usize;
-pub const OBJECT_CATEGORY_BITS: usize = 4;
+pub const OBJECT_CATEGORY_BITS: usize = bits_for(ObjectCategory::COUNT);
 pub const OBJECT_ID_BITS: usize = 13;
 pub const MIN_OBJECT_SCALE: f64 = 0.25;
 pub const MAX_OBJECT_SCALE: f64 = 3.0;
 pub const VARIATION_BITS: usize = 3;
+pub const GAME_REJECT_TYPE_BITS: usize = 2;
+pub const TEAM_MESSAGE_TYPE_BITS: usize = bits_for(6);
+pub const TEAM_PLAYERS_BITS: usize = 8;
+pub const HEALTH_BITS: usize = 8;
+pub const ADRENALINE_BITS: usize = 8;
+pub const Z_INDEX_BITS: usize = bits_for(ZIndexes::COUNT);
+/// Upper bound on the length of definition id strings (skins, badges) in client packets
+pub const ID_STRING_MAX_BYTES: usize = 64;
 
 #[derive(Clone, Debug)]
 pub struct SuroiBitStream {
@@ -26,6 +37,11 @@ impl SuroiBitStream {
             internal: BitStream::new(bytes),
         }
     }
+
+    /// Sets the stream's index, in bits
+    pub fn set_index(&mut self, index: usize) {
+        self.internal.set_index(index);
+    }
 }
 
 // forwarded methods
@@ -69,17 +85,11 @@ impl Stream for SuroiBitStream {
 
 impl SuroiBitStream {
     pub fn write_float(&mut self, value: f64, min: f64, max: f64, bit_count: usize) {
-        self.write_bits_us(
-            ((Into::<f64>::into(value).clamp(min, max) - min) / (max - min)
-                * (((1u128 << bit_count) - 1) as f64)
-                + 0.5)
-                .trunc() as u32,
-            bit_count,
-        );
+        self.write_bits_us(FixedPoint::new(min, max, bit_count).encode(value), bit_count);
     }
 
     pub fn read_float(&mut self, min: f64, max: f64, bit_count: usize) -> f64 {
-        min + (max - min) * (self.read_bits(bit_count) as f64) / ((1u128 << bit_count) - 1) as f64
+        FixedPoint::new(min, max, bit_count).decode(self.read_bits(bit_count))
     }
 
     pub fn write_vector(
@@ -132,21 +142,31 @@ impl SuroiBitStream {
     }
 
     pub fn write_position(&mut self, vec: Vec2D) {
+        let max_position = GAME_CONSTANTS.max_position as f64;
+        // out-of-bounds positions would silently get clamped by write_float
+        debug_assert!(
+            (0.0..=max_position).contains(&vec.x) && (0.0..=max_position).contains(&vec.y),
+            "Position ({}, {}) is outside of the map bounds [0, {}]",
+            vec.x,
+            vec.y,
+            max_position
+        );
+
         self.write_vector(
             vec,
             0.0,
+            max_position,
             0.0,
-            GAME_CONSTANTS.max_position as f64,
-            GAME_CONSTANTS.max_position as f64,
+            max_position,
             16,
         );
     }
 
     pub fn read_position(&mut self) -> Vec2D {
         self.read_vector(
-            0.0,
             0.0,
             GAME_CONSTANTS.max_position as f64,
+            0.0,
             GAME_CONSTANTS.max_position as f64,
             16,
         )
@@ -172,6 +192,38 @@ impl SuroiBitStream {
         self.read_float(MIN_OBJECT_SCALE, MAX_OBJECT_SCALE, bit_count);
     }
 
+    /// Quantizes health in `[0, default_health]` into `HEALTH_BITS` bits;
+    /// the round-trip error is at most half a step (`default_health / 510`)
+    pub fn write_health(&mut self, hp: f32) {
+        self.write_float(hp as f64, 0.0, GAME_CONSTANTS.player.default_health as f64, HEALTH_BITS);
+    }
+
+    pub fn read_health(&mut self) -> f32 {
+        self.read_float(0.0, GAME_CONSTANTS.player.default_health as f64, HEALTH_BITS) as f32
+    }
+
+    /// Quantizes adrenaline in `[0, max_adrenaline]` into `ADRENALINE_BITS` bits;
+    /// the round-trip error is at most half a step (`max_adrenaline / 510`)
+    pub fn write_adrenaline(&mut self, adrenaline: f32) {
+        self.write_float(adrenaline as f64, 0.0, GAME_CONSTANTS.player.max_adrenaline as f64, ADRENALINE_BITS);
+    }
+
+    pub fn read_adrenaline(&mut self) -> f32 {
+        self.read_float(0.0, GAME_CONSTANTS.player.max_adrenaline as f64, ADRENALINE_BITS) as f32
+    }
+
+    pub fn write_z_index(&mut self, z_index: ZIndexes) {
+        self.write_bits_us(z_index.as_index() as u32, Z_INDEX_BITS);
+    }
+
+    pub fn read_z_index(&mut self) -> Result<ZIndexes, StreamError> {
+        let index = self.read_bits(Z_INDEX_BITS) as usize;
+        ZIndexes::try_from_index(index).ok_or_else(|| StreamError::InvalidField {
+            field: "z-index",
+            reason: format!("no layer has index {}", index),
+        })
+    }
+
     pub fn write_variation(&mut self, variation: u8) {
         self.write_bits_us(variation, VARIATION_BITS);
     }
@@ -180,6 +232,114 @@ impl SuroiBitStream {
         self.read_bits(VARIATION_BITS) as u8
     }
 
+    /// Writes the protocol version; handshake packets start with this
+    pub fn write_protocol_version(&mut self) {
+        self.write_uint16(GAME_CONSTANTS.protocol_version);
+    }
+
+    /// Reads the peer's protocol version, erroring if it isn't ours
+    pub fn read_and_check_protocol_version(&mut self) -> Result<(), StreamError> {
+        self.ensure_bits(16)?;
+        let received = self.read_uint16();
+        let expected = GAME_CONSTANTS.protocol_version;
+
+        if received != expected {
+            return Err(StreamError::ProtocolMismatch { expected, received });
+        }
+        Ok(())
+    }
+
+    /// Errors instead of letting a read of `bits` bits run past the end of the stream
+    pub(crate) fn ensure_bits(&self, bits: usize) -> Result<(), StreamError> {
+        let available = self.bits_left();
+        if available < bits {
+            return Err(StreamError::UnexpectedEnd { needed: bits, available });
+        }
+        Ok(())
+    }
+
+    pub fn write_join(&mut self, packet: &JoinPacket) {
+        self.write_protocol_version();
+        self.write_player_name(&packet.name);
+        self.write_ascii_string(&packet.skin, None);
+        self.write_optional(packet.badge.as_deref(), |stream, badge| stream.write_ascii_string(badge, None));
+    }
+
+    /// Reads a join packet sent by a client. Since this comes straight from the network,
+    /// every field is checked and bad ones are reported as errors rather than panicking.
+    /// Blank names are replaced with the default one. Like on the client, the name always
+    /// takes up `name_max_length` bytes: longer names are cut to fit when written, so an
+    /// over-length name can't be told apart from one that was exactly that long
+    pub fn read_join(&mut self) -> Result<JoinPacket, StreamError> {
+        self.read_and_check_protocol_version()?;
+
+        let name = match self.read_checked_player_name()?.trim() {
+            "" => GAME_CONSTANTS.player.default_name.to_string(),
+            trimmed => trimmed.to_string(),
+        };
+
+        let skin = self.read_client_string("skin", ID_STRING_MAX_BYTES)?;
+        self.ensure_bits(1)?;
+        let badge = match self.read_boolean() {
+            true => Some(self.read_client_string("badge", ID_STRING_MAX_BYTES)?),
+            false => None,
+        };
+
+        Ok(JoinPacket { name, skin, badge })
+    }
+
+    /// Same field as `read_player_name`, but errors on truncated input and names with
+    /// non-printable or non-ASCII characters instead of panicking
+    fn read_checked_player_name(&mut self) -> Result<String, StreamError> {
+        let length = GAME_CONSTANTS.player.name_max_length as usize;
+        self.ensure_bits(length * 8)?;
+
+        let bytes: Vec<u8> = (0..length).map(|_| self.read_uint8()).collect();
+        let name = bytes.split(|byte| *byte == 0).next().unwrap_or_default();
+
+        if name.iter().any(|byte| !(0x20..=0x7E).contains(byte)) {
+            return Err(StreamError::InvalidField {
+                field: "name",
+                reason: String::from("contains non-printable or non-ASCII characters"),
+            });
+        }
+        Ok(String::from_utf8_lossy(name).into_owned())
+    }
+
+    /// Reads a null-terminated ASCII string of at most `max_bytes` bytes without trusting
+    /// the input: overlong, unterminated or non-ASCII strings produce an error
+    fn read_client_string(&mut self, field: &'static str, max_bytes: usize) -> Result<String, StreamError> {
+        let mut bytes = Vec::new();
+
+        loop {
+            if self.bits_left() < 8 {
+                return Err(StreamError::InvalidField {
+                    field,
+                    reason: String::from("missing its null terminator"),
+                });
+            }
+
+            match self.read_uint8() {
+                0 => break,
+                _ if bytes.len() == max_bytes => {
+                    return Err(StreamError::InvalidField {
+                        field,
+                        reason: format!("longer than {} bytes", max_bytes),
+                    });
+                }
+                byte => bytes.push(byte),
+            }
+        }
+
+        String::from_utf8(bytes)
+            .ok()
+            .filter(|string| string.is_ascii())
+            .ok_or_else(|| StreamError::InvalidField {
+                field,
+                reason: String::from("contains non-ASCII characters"),
+            })
+    }
+
     pub fn write_player_name(&mut self, name: &str) {
         self.write_ascii_string(name, Some(GAME_CONSTANTS.player.name_max_length as usize));
     }
@@ -188,6 +348,160 @@ impl SuroiBitStream {
         self.read_ascii_string(Some(GAME_CONSTANTS.player.name_max_length as usize))
     }
 
+    pub fn write_game_response(&mut self, response: &GameResponse) {
+        match response {
+            GameResponse::Success { game_id } => {
+                self.write_boolean(true);
+                self.write_uint32(*game_id);
+            }
+            GameResponse::Failure { message, reason, report_id } => {
+                self.write_boolean(false);
+                self.write_bits_us(*message as u32, GAME_REJECT_TYPE_BITS);
+                self.write_utf8_string(reason, None);
+                self.write_utf8_string(report_id, None);
+            }
+        }
+    }
+
+    pub fn read_game_response(&mut self) -> Result<GameResponse, StreamError> {
+        if self.read_boolean() {
+            return Ok(GameResponse::Success {
+                game_id: self.read_uint32(),
+            });
+        }
+
+        let message = match self.read_bits(GAME_REJECT_TYPE_BITS) {
+            0 => GameRejectType::Warn,
+            1 => GameRejectType::Temp,
+            2 => GameRejectType::Perma,
+            other => {
+                return Err(StreamError::InvalidField {
+                    field: "game reject type",
+                    reason: format!("unknown type {}", other),
+                })
+            }
+        };
+
+        Ok(GameResponse::Failure {
+            message,
+            reason: self.read_utf8_string(None),
+            report_id: self.read_utf8_string(None),
+        })
+    }
+
+    /// Writes a presence bit, followed by the value using `writer` if there is one
+    pub fn write_optional<T>(&mut self, value: Option<T>, writer: impl FnOnce(&mut Self, T)) {
+        self.write_boolean(value.is_some());
+        if let Some(value) = value {
+            writer(self, value);
+        }
+    }
+
+    /// Reads a presence bit, followed by the value using `reader` if it's set
+    pub fn read_optional<T>(&mut self, reader: impl FnOnce(&mut Self) -> T) -> Option<T> {
+        if self.read_boolean() {
+            Some(reader(self))
+        } else {
+            None
+        }
+    }
+
+    /// Writes an emote, or a map ping when it comes with the position it was placed at
+    pub fn write_emote(&mut self, emote_id: u16, position: Option<Vec2D>) {
+        self.write_uint16(emote_id);
+        self.write_optional(position, Self::write_position);
+    }
+
+    pub fn read_emote(&mut self) -> (u16, Option<Vec2D>) {
+        (self.read_uint16(), self.read_optional(Self::read_position))
+    }
+
+    pub fn write_team_player_info(&mut self, info: &CustomTeamPlayerInfo) {
+        self.write_uint32(info.id);
+        self.write_optional(info.is_leader, Self::write_boolean);
+        self.write_player_name(&info.name);
+        self.write_utf8_string(&info.skin, None);
+        self.write_optional(info.badge.as_deref(), |stream, badge| stream.write_utf8_string(badge, None));
+        self.write_optional(info.name_color, Self::write_int32);
+    }
+
+    pub fn read_team_player_info(&mut self) -> CustomTeamPlayerInfo<'static> {
+        CustomTeamPlayerInfo {
+            id: self.read_uint32(),
+            is_leader: self.read_optional(Self::read_boolean),
+            name: Cow::Owned(self.read_player_name()),
+            skin: Cow::Owned(self.read_utf8_string(None)),
+            badge: self.read_optional(|stream| Cow::Owned(stream.read_utf8_string(None))),
+            name_color: self.read_optional(Self::read_int32),
+        }
+    }
+
+    pub fn write_team_message(&mut self, message: &CustomTeamMessage) {
+        match message {
+            CustomTeamMessage::Join { id, team_id, is_leader, auto_fill, locked, players } => {
+                self.write_bits_us(0u32, TEAM_MESSAGE_TYPE_BITS);
+                self.write_uint32(*id);
+                self.write_utf8_string(team_id, None);
+                self.write_boolean(*is_leader);
+                self.write_boolean(*auto_fill);
+                self.write_boolean(*locked);
+                self.write_bits_us(players.len() as u32, TEAM_PLAYERS_BITS);
+                for player in players.iter() {
+                    self.write_team_player_info(player);
+                }
+            }
+            CustomTeamMessage::PlayerJoin(info) => {
+                self.write_bits_us(1u32, TEAM_MESSAGE_TYPE_BITS);
+                self.write_team_player_info(info);
+            }
+            CustomTeamMessage::PlayerLeave { id, new_leader_id } => {
+                self.write_bits_us(2u32, TEAM_MESSAGE_TYPE_BITS);
+                self.write_uint32(*id);
+                self.write_optional(*new_leader_id, Self::write_uint32);
+            }
+            CustomTeamMessage::Settings { auto_fill, locked } => {
+                self.write_bits_us(3u32, TEAM_MESSAGE_TYPE_BITS);
+                self.write_optional(*auto_fill, Self::write_boolean);
+                self.write_optional(*locked, Self::write_boolean);
+            }
+            CustomTeamMessage::Start => self.write_bits_us(4u32, TEAM_MESSAGE_TYPE_BITS),
+            CustomTeamMessage::Started => self.write_bits_us(5u32, TEAM_MESSAGE_TYPE_BITS),
+        }
+    }
+
+    pub fn read_team_message(&mut self) -> Result<CustomTeamMessage<'static>, StreamError> {
+        Ok(match self.read_bits(TEAM_MESSAGE_TYPE_BITS) {
+            0 => CustomTeamMessage::Join {
+                id: self.read_uint32(),
+                team_id: self.read_utf8_string(None),
+                is_leader: self.read_boolean(),
+                auto_fill: self.read_boolean(),
+                locked: self.read_boolean(),
+                players: {
+                    let length = self.read_bits(TEAM_PLAYERS_BITS);
+                    (0..length).map(|_| self.read_team_player_info()).collect()
+                },
+            },
+            1 => CustomTeamMessage::PlayerJoin(self.read_team_player_info()),
+            2 => CustomTeamMessage::PlayerLeave {
+                id: self.read_uint32(),
+                new_leader_id: self.read_optional(Self::read_uint32),
+            },
+            3 => CustomTeamMessage::Settings {
+                auto_fill: self.read_optional(Self::read_boolean),
+                locked: self.read_optional(Self::read_boolean),
+            },
+            4 => CustomTeamMessage::Start,
+            5 => CustomTeamMessage::Started,
+            other => {
+                return Err(StreamError::InvalidField {
+                    field: "custom team message type",
+                    reason: format!("unknown type {}", other),
+                })
+            }
+        })
+    }
+
     pub fn write_array<T>(
         &mut self,
         arr: &Vec<T>,
@@ -1,9 +1,13 @@
-use std::ops::{Add, Mul, Sub, Neg};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign, Neg};
 use std::cmp::PartialEq;
 
 use crate::typings::Orientation;
+use crate::utils::bitstream::{Deserialize, Serialize, Stream};
+use crate::utils::hitbox::{hitbox_intersects_line, Hitbox};
+use crate::utils::math::geometry;
 
 #[derive(Clone, Debug, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vec2D {
     pub x: f64,
     pub y: f64
@@ -26,6 +30,13 @@ impl Add for Vec2D {
     }
 }
 
+impl AddAssign for Vec2D {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
 impl Sub for Vec2D {
     type Output = Vec2D;
 
@@ -37,11 +48,20 @@ impl Sub for Vec2D {
     }
 }
 
+impl SubAssign for Vec2D {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+/// Dot product. Kept for backwards compatibility; prefer `Vec2D::dot`, which
+/// can't be mistaken for `Mul<f64>` scaling
 impl Mul for Vec2D {
     type Output = f64;
 
     fn mul(self, other: Vec2D) -> f64 {
-        self.x * other.x + self.y * other.y
+        self.dot(other)
     }
 }
 
@@ -56,6 +76,34 @@ impl Mul<f64> for Vec2D {
     }
 }
 
+impl MulAssign<f64> for Vec2D {
+    fn mul_assign(&mut self, rhs: f64) {
+        self.x *= rhs;
+        self.y *= rhs;
+    }
+}
+
+/// Like regular float division, dividing by zero doesn't panic: components become
+/// `±inf`, or `NaN` for components that were 0
+impl Div<f64> for Vec2D {
+    type Output = Self;
+
+    fn div(self, rhs: f64) -> Self {
+        Vec2D {
+            x: self.x / rhs,
+            y: self.y / rhs
+        }
+    }
+}
+
+/// See `Div<f64>` for the behavior when dividing by zero
+impl DivAssign<f64> for Vec2D {
+    fn div_assign(&mut self, rhs: f64) {
+        self.x /= rhs;
+        self.y /= rhs;
+    }
+}
+
 impl Neg for Vec2D {
     type Output = Vec2D;
 
@@ -67,6 +115,45 @@ impl Neg for Vec2D {
     }
 }
 
+impl From<(f64, f64)> for Vec2D {
+    fn from((x, y): (f64, f64)) -> Self {
+        Vec2D { x, y }
+    }
+}
+
+impl From<[f64; 2]> for Vec2D {
+    fn from([x, y]: [f64; 2]) -> Self {
+        Vec2D { x, y }
+    }
+}
+
+impl From<Vec2D> for (f64, f64) {
+    fn from(vec: Vec2D) -> Self {
+        (vec.x, vec.y)
+    }
+}
+
+impl From<Vec2D> for [f64; 2] {
+    fn from(vec: Vec2D) -> Self {
+        vec.to_array()
+    }
+}
+
+/// Full-precision vector, as two float32s; positions on the map should use
+/// `SuroiBitStream::write_position` instead
+impl Serialize for Vec2D {
+    fn write_to(&self, stream: &mut impl Stream) {
+        stream.write_float32(self.x);
+        stream.write_float32(self.y);
+    }
+}
+
+impl Deserialize for Vec2D {
+    fn read_from(stream: &mut impl Stream) -> Self {
+        Vec2D::new(stream.read_float32() as f64, stream.read_float32() as f64)
+    }
+}
+
 impl Vec2D {
     pub fn new(x: f64, y: f64) -> Self {
         Vec2D {
@@ -75,6 +162,30 @@ impl Vec2D {
         }
     }
 
+    pub const fn zero() -> Self {
+        Vec2D { x: 0.0, y: 0.0 }
+    }
+
+    pub const fn one() -> Self {
+        Vec2D { x: 1.0, y: 1.0 }
+    }
+
+    pub const fn unit_x() -> Self {
+        Vec2D { x: 1.0, y: 0.0 }
+    }
+
+    pub const fn unit_y() -> Self {
+        Vec2D { x: 0.0, y: 1.0 }
+    }
+
+    pub fn dot(self, other: Vec2D) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    pub fn to_array(self) -> [f64; 2] {
+        [self.x, self.y]
+    }
+
     pub fn scale(self, scalar: f64) -> Self {
         Vec2D {
             x: self.x * scalar,
@@ -98,6 +209,45 @@ impl Vec2D {
         }
     }
 
+    /// Rotates this vector by `angle` about `pivot` instead of the origin
+    pub fn rotate_around(self, pivot: Vec2D, angle: f64) -> Self {
+        (self - pivot).rotate(angle) + pivot
+    }
+
+    /// Rotates this vector by `angle` and scales it by `scale`, both about `pivot`,
+    /// then translates it by `translate`, all in one go
+    pub fn transform(self, pivot: Vec2D, scale: f64, angle: f64, translate: Vec2D) -> Self {
+        let cos: f64 = angle.cos();
+        let sin: f64 = angle.sin();
+        let x = self.x - pivot.x;
+        let y = self.y - pivot.y;
+        Vec2D {
+            x: pivot.x + (x * cos - y * sin) * scale + translate.x,
+            y: pivot.y + (x * sin + y * cos) * scale + translate.y
+        }
+    }
+
+    /// Same as `perpendicular_ccw`
+    pub fn perpendicular(self) -> Self {
+        self.perpendicular_ccw()
+    }
+
+    /// Rotates this vector by 90° counter-clockwise (in a y-up coordinate system)
+    pub fn perpendicular_ccw(self) -> Self {
+        Vec2D {
+            x: -self.y,
+            y: self.x
+        }
+    }
+
+    /// Rotates this vector by 90° clockwise (in a y-up coordinate system)
+    pub fn perpendicular_cw(self) -> Self {
+        Vec2D {
+            x: self.y,
+            y: -self.x
+        }
+    }
+
     pub fn squared_length(self) -> f64 {
         self.x * self.x + self.y * self.y
     }
@@ -106,24 +256,45 @@ impl Vec2D {
         f64::sqrt(self.squared_length())
     }
 
+    pub fn distance_to(self, other: Vec2D) -> f64 {
+        geometry::distance(self, other)
+    }
+
+    pub fn distance_squared_to(self, other: Vec2D) -> f64 {
+        geometry::distance_squared(self, other)
+    }
+
     pub fn direction(self) -> f64 {
         f64::atan2(self.y, self.x)
     }
 
     pub fn angle(self, vec2: Vec2D) -> f64  {
-        f64::acos((self.x * vec2.x + self.y * vec2.y) / f64::sqrt(self.length() * vec2.length()))
+        // floating-point error can push the cosine slightly outside of [-1, 1], where acos gives NaN
+        f64::acos((self.dot(vec2) / (self.length() * vec2.length())).clamp(-1.0, 1.0))
     }
 
     pub fn lerp(self, end: Vec2D, interp_factor: f64) -> Self {
         self * (1.0 - interp_factor) + end * interp_factor
     }
 
+    /// Moves at most `max_delta` units towards `target`, landing on it exactly once within range
+    pub fn move_towards(self, target: Vec2D, max_delta: f64) -> Self {
+        let delta = target - self;
+        let distance = delta.length();
+
+        if distance <= max_delta || distance == 0.0 {
+            target
+        } else {
+            self + delta.normalize(None) * max_delta
+        }
+    }
+
     pub fn project(self, vec2: Vec2D) -> Self {
-        vec2 * (self * vec2 / vec2.squared_length())
+        vec2 * (self.dot(vec2) / vec2.squared_length())
     }
 
     pub fn normalize(self, fallback: Option<Vec2D>) -> Self {
-        let fallback: Vec2D = fallback.unwrap_or(Vec2D::new(1.0, 0.0));
+        let fallback: Vec2D = fallback.unwrap_or(Vec2D::unit_x());
         let len = self.length();
 
         if len > 0.000001 {
@@ -136,6 +307,143 @@ impl Vec2D {
         }
     }
 
+    pub fn is_finite(self) -> bool {
+        self.x.is_finite() && self.y.is_finite()
+    }
+
+    /// Returns `fallback` if either component is NaN or infinite
+    pub fn sanitize(self, fallback: Vec2D) -> Self {
+        if self.is_finite() {
+            self
+        } else {
+            fallback
+        }
+    }
+
+    /// Scales this vector down so that its length doesn't exceed `max`
+    pub fn clamp_length(self, max: f64) -> Self {
+        if self.squared_length() > max * max {
+            self.with_length(max)
+        } else {
+            self
+        }
+    }
+
+    /// Scales this vector to the given length; the zero vector stays as is
+    pub fn with_length(self, len: f64) -> Self {
+        if self.squared_length() == 0.0 {
+            return self;
+        }
+        self.normalize(None) * len
+    }
+
+    pub fn min_components(self, other: Vec2D) -> Self {
+        Vec2D {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y)
+        }
+    }
+
+    pub fn max_components(self, other: Vec2D) -> Self {
+        Vec2D {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y)
+        }
+    }
+
+    pub fn min_component(self) -> f64 {
+        self.x.min(self.y)
+    }
+
+    pub fn max_component(self) -> f64 {
+        self.x.max(self.y)
+    }
+
+    pub fn floor(self) -> Self {
+        Vec2D {
+            x: self.x.floor(),
+            y: self.y.floor()
+        }
+    }
+
+    pub fn ceil(self) -> Self {
+        Vec2D {
+            x: self.x.ceil(),
+            y: self.y.ceil()
+        }
+    }
+
+    pub fn round(self) -> Self {
+        Vec2D {
+            x: self.x.round(),
+            y: self.y.round()
+        }
+    }
+
+    /// Rounds both components to the nearest multiple of `precision` and returns the
+    /// multiples as an integer key that can be hashed, so that near-identical points
+    /// share a key. Points straddling a rounding boundary can still get different keys
+    /// even if they're closer than `precision` to each other
+    pub fn quantized_key(self, precision: f64) -> (i64, i64) {
+        (
+            (self.x / precision).round() as i64,
+            (self.y / precision).round() as i64
+        )
+    }
+
+    /// Moves this vector to the nearest grid intersection, for a grid of square cells
+    /// with a side length of `cell`
+    pub fn snap_to_grid(self, cell: f64) -> Self {
+        Vec2D {
+            x: (self.x / cell).round() * cell,
+            y: (self.y / cell).round() * cell
+        }
+    }
+
+    /// Returns the indices of the grid cell this vector lies in, for a grid of square
+    /// cells with a side length of `cell`. Points on a cell's edge belong to the cell
+    /// after it, so the cell `(0, 0)` covers `[0, cell)` on both axes
+    pub fn grid_cell(self, cell: f64) -> (i64, i64) {
+        (
+            (self.x / cell).floor() as i64,
+            (self.y / cell).floor() as i64
+        )
+    }
+
+    /// Packs the grid cell this vector lies in (see `grid_cell`) into a single row-major
+    /// index, `row * grid_width + column`.
+    ///
+    /// Valid coordinates are `0 <= x < cell * grid_width` and `0 <= y < cell * (u32::MAX / grid_width)`.
+    /// Anything else panics, even in release builds, since it would otherwise wrap around
+    /// to the index of a different cell
+    pub fn to_packed_cell(self, cell: f64, grid_width: u32) -> u32 {
+        let (column, row) = self.grid_cell(cell);
+        assert!(
+            (0..grid_width as i64).contains(&column) && (0..(u32::MAX / grid_width) as i64).contains(&row),
+            "Vector {:?} is outside of a grid {} cells wide",
+            self,
+            grid_width
+        );
+
+        row as u32 * grid_width + column as u32
+    }
+
+    /// Returns the center of the cell with the given index, the inverse of `to_packed_cell`
+    pub fn from_packed_cell(index: u32, cell: f64, grid_width: u32) -> Self {
+        Vec2D {
+            x: ((index % grid_width) as f64 + 0.5) * cell,
+            y: ((index / grid_width) as f64 + 0.5) * cell
+        }
+    }
+
+    /// Returns the exact bit patterns of both components, for use as a `HashMap` or
+    /// `HashSet` key; vectors that compare equal give equal keys (`-0.0` is treated
+    /// like `0.0`). NaN components aren't supported, since NaN never equals itself
+    pub fn to_bits_key(self) -> (u64, u64) {
+        // adding 0.0 turns -0.0 into 0.0 and leaves everything else untouched
+        ((self.x + 0.0).to_bits(), (self.y + 0.0).to_bits())
+    }
+
     pub fn equals(self, vec2: Vec2D, epsilon: Option<f64>) -> bool {
         let epsilon: f64 = epsilon.unwrap_or(0.001);
         f64::abs(self.x - vec2.x) <= epsilon && f64::abs(self.y - vec2.y) <= epsilon
@@ -152,4 +460,30 @@ impl Vec2D {
     pub fn add_adjust(self, pos2: Vec2D, orientation: Orientation) -> Vec2D {
         self + pos2.rotate(orientation.to_angle())
     }
+
+    /// Multiplies the components of both vectors pairwise. `Mul<Vec2D>` can't be used
+    /// for this since it's the dot product
+    pub fn component_mul(self, other: Vec2D) -> Vec2D {
+        Vec2D {
+            x: self.x * other.x,
+            y: self.y * other.y
+        }
+    }
+
+    /// Reflects this vector across a surface with the given normal, which must be unit length
+    pub fn reflect(self, normal: Vec2D) -> Vec2D {
+        self - normal * (2.0 * self.dot(normal))
+    }
+
+    /// Same as `reflect`, but normalizes `normal` first
+    pub fn reflect_unnormalized(self, normal: Vec2D) -> Vec2D {
+        self.reflect(normal.normalize(None))
+    }
+
+    /// Moves this position by `velocity` and, if that runs into `hitbox`, returns the
+    /// point of contact along with `velocity` reflected off the surface that was hit
+    pub fn bounce(self, velocity: Vec2D, hitbox: &Hitbox) -> Option<(Vec2D, Vec2D)> {
+        hitbox_intersects_line(hitbox, self, self + velocity)
+            .map(|hit| (hit.point, velocity.reflect_unnormalized(hit.normal)))
+    }
 }